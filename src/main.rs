@@ -9,5 +9,11 @@ mod tests;
 fn main() {
     let contents = fs::read_to_string("./source.lang")
         .expect("File does not exist or cannot be read for some other reason");
-    println!("{:?}", program().parse(contents.as_str()));
+    match parse_program(contents.as_str()) {
+        Ok((program, literals)) => {
+            println!("{:?}", program);
+            println!("{:?}", literals);
+        }
+        Err(diagnostics) => eprint!("{}", diagnostics),
+    }
 }
@@ -1,6 +1,226 @@
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
 
-pub struct Parser<'a, T: 'a>(Arc<dyn Fn(&'a str) -> Result<(T, &'a str), &'static str> + 'a>);
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn start() -> Self {
+        Position {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(node: T, span: Span) -> Self {
+        Spanned { node, span }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Input<'a> {
+    source: &'a str,
+    position: Position,
+    token_end: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Input {
+            source,
+            position: Position::start(),
+            token_end: 0,
+        }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.position.offset..]
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn token_end(&self) -> usize {
+        self.token_end
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub fn advance(self, bytes: usize) -> Self {
+        let position = self.moved(bytes);
+
+        Input {
+            position,
+            token_end: position.offset,
+            ..self
+        }
+    }
+
+    pub fn skip(self, bytes: usize) -> Self {
+        Input {
+            position: self.moved(bytes),
+            ..self
+        }
+    }
+
+    fn moved(&self, bytes: usize) -> Position {
+        let mut position = self.position;
+        for c in self.rest()[..bytes].chars() {
+            if c == '\n' {
+                position.line += 1;
+                position.column = 1;
+            } else {
+                position.column += 1;
+            }
+        }
+        position.offset += bytes;
+        position
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ParseError {
+    pub position: Position,
+    pub expected: Vec<String>,
+    pub found: Option<String>,
+    pub message: Option<String>,
+    pub committed: bool,
+}
+
+impl ParseError {
+    pub fn at(input: Input) -> Self {
+        ParseError {
+            position: input.position(),
+            expected: vec![],
+            found: input.peek().map(|c| quote(&c.to_string())),
+            message: None,
+            committed: false,
+        }
+    }
+
+    pub fn expected<S: Into<String>>(input: Input, expected: S) -> Self {
+        ParseError {
+            expected: vec![expected.into()],
+            ..ParseError::at(input)
+        }
+    }
+
+    pub fn expected_one_of<I, S>(input: Input, expected: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ParseError {
+            expected: expected.into_iter().map(Into::into).collect(),
+            ..ParseError::at(input)
+        }
+    }
+
+    pub fn message<S: Into<String>>(input: Input, message: S) -> Self {
+        ParseError {
+            message: Some(message.into()),
+            ..ParseError::at(input)
+        }
+    }
+
+    pub fn merge(self, other: ParseError) -> Self {
+        if self.position.offset > other.position.offset {
+            return self;
+        }
+        if other.position.offset > self.position.offset {
+            return other;
+        }
+
+        let mut expected = self.expected;
+        for item in other.expected {
+            if !expected.contains(&item) {
+                expected.push(item);
+            }
+        }
+
+        ParseError {
+            position: self.position,
+            expected,
+            found: self.found.or(other.found),
+            message: self.message.or(other.message),
+            committed: self.committed || other.committed,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.position)?;
+
+        if let Some(message) = &self.message {
+            return write!(f, "{}", message);
+        }
+
+        let found = self.found.as_deref().unwrap_or("end of input");
+        match self.expected.split_last() {
+            Some((last, [])) => write!(f, "expected {}, found {}", last, found),
+            Some((last, init)) => write!(
+                f,
+                "expected {} or {}, found {}",
+                init.join(", "),
+                last,
+                found
+            ),
+            None => write!(f, "unexpected {}", found),
+        }
+    }
+}
+
+pub fn quote(text: &str) -> String {
+    let escaped: String = text
+        .chars()
+        .map(|c| {
+            if c.is_control() {
+                c.escape_default().to_string()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect();
+    format!("`{}`", escaped)
+}
+
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+pub struct Parser<'a, T: 'a>(Arc<dyn Fn(Input<'a>) -> ParseResult<'a, T> + 'a>);
 
 impl<'a, T: 'a> Clone for Parser<'a, T> {
     fn clone(&self) -> Self {
@@ -11,7 +231,7 @@ impl<'a, T: 'a> Clone for Parser<'a, T> {
 impl<'a, T> Parser<'a, T> {
     pub fn new<F>(parser: F) -> Self
     where
-        F: 'a + Fn(&'a str) -> Result<(T, &'a str), &'static str>,
+        F: 'a + Fn(Input<'a>) -> ParseResult<'a, T>,
     {
         Self(Arc::new(parser))
     }
@@ -20,9 +240,9 @@ impl<'a, T> Parser<'a, T> {
     where
         F: Fn() -> Self + 'a,
     {
-        Parser::new(move |input: &'a str| {
+        Parser::new(move |input: Input<'a>| {
             let parser = parser_factory();
-            parser.parse(input)
+            parser.run(input)
         })
     }
 
@@ -31,27 +251,92 @@ impl<'a, T> Parser<'a, T> {
         F: 'a + Fn(T) -> U,
         U: 'a,
     {
-        Parser::new(move |input: &'a str| {
-            self.parse(input)
+        Parser::new(move |input: Input<'a>| {
+            self.run(input)
                 .map(|(result, remaining)| (func(result), remaining))
         })
     }
 
+    pub fn try_map<U, F>(self, func: F) -> Parser<'a, U>
+    where
+        F: 'a + Fn(T) -> Result<U, String>,
+        U: 'a,
+    {
+        Parser::new(move |input: Input<'a>| {
+            let (result, remaining) = self.run(input)?;
+            func(result)
+                .map(|result| (result, remaining))
+                .map_err(|message| ParseError::message(input, message))
+        })
+    }
+
+    pub fn validate<U, F>(self, func: F) -> Parser<'a, U>
+    where
+        F: 'a + Fn(T) -> Result<U, String>,
+        U: 'a,
+    {
+        Parser::new(move |input: Input<'a>| {
+            let (result, remaining) = self.run(input)?;
+            func(result)
+                .map(|result| (result, remaining))
+                .map_err(|message| ParseError {
+                    committed: true,
+                    ..ParseError::message(input, message)
+                })
+        })
+    }
+
     pub fn pure(a: T) -> Self
     where
         T: 'a + Clone,
     {
-        Parser::new(move |input: &'a str| Ok((a.clone(), input)))
+        Parser::new(move |input: Input<'a>| Ok((a.clone(), input)))
     }
 
     pub fn many(self) -> Parser<'a, Vec<T>> {
-        Parser::new(move |mut input: &'a str| {
+        Parser::new(move |mut input: Input<'a>| {
             let mut results = Vec::new();
-            while let Ok((result, remaining)) = self.parse(input) {
-                results.push(result);
-                input = remaining;
+            loop {
+                match self.run(input) {
+                    Ok((_, remaining)) if remaining.position() == input.position() => {
+                        return Ok((results, input))
+                    }
+                    Ok((result, remaining)) => {
+                        results.push(result);
+                        input = remaining;
+                    }
+                    Err(error) if error.committed => return Err(error),
+                    Err(_) => return Ok((results, input)),
+                }
+            }
+        })
+    }
+
+    pub fn many_till<U>(self, end: Parser<'a, U>) -> Parser<'a, Vec<T>>
+    where
+        U: 'a,
+    {
+        Parser::new(move |mut input: Input<'a>| {
+            let mut results = Vec::new();
+            loop {
+                let stop = match end.run(input) {
+                    Ok((_, remaining)) => return Ok((results, remaining)),
+                    Err(error) if error.committed => return Err(error),
+                    Err(error) => error,
+                };
+
+                match self.run(input) {
+                    Ok((_, remaining)) if remaining.position() == input.position() => {
+                        return Err(stop)
+                    }
+                    Ok((result, remaining)) => {
+                        results.push(result);
+                        input = remaining;
+                    }
+                    Err(error) if error.committed => return Err(error),
+                    Err(error) => return Err(stop.merge(error)),
+                }
             }
-            Ok((results, input))
         })
     }
 
@@ -68,10 +353,10 @@ impl<'a, T> Parser<'a, T> {
     where
         U: 'a,
     {
-        Parser::new(move |input: &'a str| {
-            self.parse(input).and_then(|(result_a, remaining_a)| {
+        Parser::new(move |input: Input<'a>| {
+            self.run(input).and_then(|(result_a, remaining_a)| {
                 other
-                    .parse(remaining_a)
+                    .run(remaining_a)
                     .map(|(result_b, remaining_b)| ((result_a, result_b), remaining_b))
             })
         })
@@ -83,7 +368,7 @@ impl<'a, T> Parser<'a, T> {
     {
         let merge = self.and(other);
 
-        Parser::new(move |input: &'a str| match merge.parse(input) {
+        Parser::new(move |input: Input<'a>| match merge.run(input) {
             Ok(((left, _), remaining)) => Ok((left, remaining)),
             Err(reason) => Err(reason),
         })
@@ -95,23 +380,67 @@ impl<'a, T> Parser<'a, T> {
     {
         let merge = self.and(other);
 
-        Parser::new(move |input: &'a str| match merge.parse(input) {
+        Parser::new(move |input: Input<'a>| match merge.run(input) {
             Ok(((_, right), remaining)) => Ok((right, remaining)),
             Err(reason) => Err(reason),
         })
     }
 
     pub fn empty(reason: &'static str) -> Self {
-        Parser::new(move |_| Err(reason))
+        Parser::new(move |input: Input<'a>| Err(ParseError::message(input, reason)))
     }
 
     pub fn or(self, other: Parser<'a, T>) -> Parser<'a, T> {
-        Parser::new(move |input: &'a str| self.parse(input).or_else(|_| other.parse(input)))
+        Parser::new(move |input: Input<'a>| {
+            self.run(input).or_else(|first| {
+                if first.committed {
+                    return Err(first);
+                }
+
+                other.run(input).map_err(|second| {
+                    if second.committed {
+                        second
+                    } else {
+                        first.merge(second)
+                    }
+                })
+            })
+        })
     }
 
-    pub fn parse(&self, input: &'a str) -> Result<(T, &'a str), &'static str> {
+    pub fn commit(self) -> Self {
+        Parser::new(move |input: Input<'a>| {
+            self.run(input).map_err(|error| ParseError {
+                committed: true,
+                ..error
+            })
+        })
+    }
+
+    pub fn label(self, name: &'static str) -> Self {
+        Parser::new(move |input: Input<'a>| {
+            self.run(input).map_err(|error| {
+                let start = trivia().run(input).map_or(input, |(_, rest)| rest);
+                if error.message.is_some() || error.position.offset > start.position().offset {
+                    return error;
+                }
+
+                ParseError {
+                    expected: vec![name.to_string()],
+                    ..error
+                }
+            })
+        })
+    }
+
+    pub fn run(&self, input: Input<'a>) -> ParseResult<'a, T> {
         (self.0)(input)
     }
+
+    pub fn parse(&self, input: &'a str) -> Result<(T, &'a str), ParseError> {
+        self.run(Input::new(input))
+            .map(|(result, remaining)| (result, remaining.rest()))
+    }
 }
 
 impl<'a> Parser<'a, Vec<char>> {
@@ -120,33 +449,49 @@ impl<'a> Parser<'a, Vec<char>> {
     }
 }
 
+#[allow(dead_code)]
 pub fn any<'a>() -> Parser<'a, char> {
-    Parser::new(move |input: &'a str| {
-        let mut chars = input.chars();
-        match chars.next() {
-            Some(c) => Ok((c, chars.as_str())),
-            _ => Err("Nothing to parse"),
-        }
+    Parser::new(move |input: Input<'a>| match input.peek() {
+        Some(c) => Ok((c, input.advance(c.len_utf8()))),
+        _ => Err(ParseError::expected(input, "any character")),
+    })
+}
+
+pub fn eof<'a>() -> Parser<'a, ()> {
+    Parser::new(move |input: Input<'a>| match input.peek() {
+        None => Ok(((), input)),
+        _ => Err(ParseError::expected(input, "end of input")),
     })
 }
 
 pub fn char<'a>(expected: char) -> Parser<'a, char> {
-    Parser::new(move |input: &'a str| {
-        let mut chars = input.chars();
-        match chars.next() {
-            Some(c) if c == expected => Ok((c, chars.as_str())),
-            _ => Err("Character mismatch"),
-        }
+    Parser::new(move |input: Input<'a>| match input.peek() {
+        Some(c) if c == expected => Ok((c, input.advance(c.len_utf8()))),
+        _ => Err(ParseError::expected(input, quote(&expected.to_string()))),
     })
 }
 
 pub fn list<'a>(allowed: &[char]) -> Parser<'a, char> {
-    let fail = Parser::empty("List parser has no members");
-    allowed.iter().fold(fail, |sum, x| sum.or(char(*x)))
+    if allowed.is_empty() {
+        return Parser::empty("List parser has no members");
+    }
+
+    let allowed = allowed.to_vec();
+    Parser::new(move |input: Input<'a>| match input.peek() {
+        Some(c) if allowed.contains(&c) => Ok((c, input.advance(c.len_utf8()))),
+        _ => Err(ParseError::expected_one_of(
+            input,
+            allowed.iter().map(|c| quote(&c.to_string())),
+        )),
+    })
 }
 
-pub fn whitespace<'a>() -> Parser<'a, char> {
-    list(&[' ', '\n', '\t', '\r'])
+pub fn none_of<'a>(disallowed: &[char]) -> Parser<'a, char> {
+    let disallowed = disallowed.to_vec();
+    Parser::new(move |input: Input<'a>| match input.peek() {
+        Some(c) if !disallowed.contains(&c) => Ok((c, input.advance(c.len_utf8()))),
+        _ => Err(ParseError::at(input)),
+    })
 }
 
 pub fn lowercase<'a>() -> Parser<'a, char> {
@@ -154,6 +499,7 @@ pub fn lowercase<'a>() -> Parser<'a, char> {
         'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r',
         's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
     ])
+    .label("lowercase letter")
 }
 
 pub fn uppercase<'a>() -> Parser<'a, char> {
@@ -161,14 +507,31 @@ pub fn uppercase<'a>() -> Parser<'a, char> {
         'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R',
         'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
     ])
+    .label("uppercase letter")
 }
 
 pub fn letter<'a>() -> Parser<'a, char> {
-    lowercase().or(uppercase()).or(char('_'))
+    lowercase().or(uppercase()).or(char('_')).label("letter")
 }
 
 pub fn digit<'a>() -> Parser<'a, char> {
-    list(&['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'])
+    list(&['0', '1', '2', '3', '4', '5', '6', '7', '8', '9']).label("digit")
+}
+
+pub fn hex_digit<'a>() -> Parser<'a, char> {
+    list(&[
+        '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f', 'A', 'B',
+        'C', 'D', 'E', 'F',
+    ])
+    .label("hex digit")
+}
+
+pub fn octal_digit<'a>() -> Parser<'a, char> {
+    list(&['0', '1', '2', '3', '4', '5', '6', '7']).label("octal digit")
+}
+
+pub fn binary_digit<'a>() -> Parser<'a, char> {
+    list(&['0', '1']).label("binary digit")
 }
 
 pub fn alphanumeric<'a>() -> Parser<'a, char> {
@@ -183,48 +546,140 @@ pub fn otherwise<'a, T: Clone>(b: Parser<'a, T>, a: T) -> Parser<'a, T> {
     b.or(Parser::pure(a))
 }
 
-pub fn delimited<'a, T: Clone, U>(a: Parser<'a, T>, sep: Parser<'a, U>) -> Parser<'a, Vec<T>> {
-    otherwise(
-        a.clone().and(sep.right(a).many()).map(|(first, mut rest)| {
-            rest.insert(0, first);
-            rest
-        }),
-        vec![],
-    )
+pub fn lookahead<'a, T: 'a>(a: Parser<'a, T>) -> Parser<'a, T> {
+    Parser::new(move |input: Input<'a>| a.run(input).map(|(result, _)| (result, input)))
 }
 
-pub fn strip<'a, T>(a: Parser<'a, T>) -> Parser<'a, T> {
-    between(whitespace().many(), a, whitespace().many())
+pub fn not<'a, T: 'a>(a: Parser<'a, T>) -> Parser<'a, ()> {
+    Parser::new(move |input: Input<'a>| match a.run(input) {
+        Ok(_) => Err(ParseError::at(input)),
+        Err(_) => Ok(((), input)),
+    })
 }
 
-pub fn string<'a>(input: &'a str) -> Parser<'a, String> {
-    if input.is_empty() {
-        return Parser::pure("".to_string());
+pub fn separated<'a, T, U>(a: Parser<'a, T>, sep: Parser<'a, U>) -> Parser<'a, Vec<T>>
+where
+    T: 'a,
+    U: 'a,
+{
+    a.clone().and(sep.right(a).many()).map(|(first, mut rest)| {
+        rest.insert(0, first);
+        rest
+    })
+}
+
+fn block_comment(input: Input) -> Result<Input, ParseError> {
+    let bytes = input.rest().as_bytes();
+    let mut depth = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index..].starts_with(b"/*") {
+            depth += 1;
+            index += 2;
+        } else if bytes[index..].starts_with(b"*/") {
+            depth -= 1;
+            index += 2;
+            if depth == 0 {
+                return Ok(input.skip(index));
+            }
+        } else {
+            index += 1;
+        }
     }
 
-    char(input.chars().next().unwrap())
-        .and(string(&input[1..]))
-        .map(|(c, rest)| {
-            let mut result = "".to_string();
-            result.push(c);
-            result.push_str(&rest);
-            result
-        })
+    Err(ParseError {
+        committed: true,
+        ..ParseError::message(input, "unterminated block comment")
+    })
+}
+
+pub fn trivia<'a>() -> Parser<'a, ()> {
+    Parser::new(move |mut input: Input<'a>| loop {
+        let rest = input.rest();
+        let trimmed = rest.trim_start_matches([' ', '\n', '\t', '\r']);
+        input = input.skip(rest.len() - trimmed.len());
+
+        if trimmed.starts_with("//") {
+            input = input.skip(trimmed.find('\n').unwrap_or(trimmed.len()));
+        } else if trimmed.starts_with("/*") {
+            input = block_comment(input)?;
+        } else {
+            return Ok(((), input));
+        }
+    })
+}
+
+pub fn spanned<'a, T: 'a>(a: Parser<'a, T>) -> Parser<'a, Spanned<T>> {
+    let leading = trivia();
+
+    Parser::new(move |input: Input<'a>| {
+        let start = leading.run(input).map_or(input, |(_, rest)| rest);
+        let (node, remaining) = a.run(input)?;
+        let start = start.position().offset.min(remaining.position().offset);
+        let end = remaining.token_end().max(start);
+        Ok((Spanned::new(node, Span::new(start, end)), remaining))
+    })
+}
+
+pub fn strip<'a, T>(a: Parser<'a, T>) -> Parser<'a, T> {
+    between(trivia(), a, trivia())
+}
+
+pub fn string<'a>(expected: &'a str) -> Parser<'a, String> {
+    Parser::new(move |input: Input<'a>| {
+        if input.rest().starts_with(expected) {
+            Ok((expected.to_string(), input.advance(expected.len())))
+        } else {
+            Err(ParseError::expected(input, quote(expected)))
+        }
+    })
 }
 
-pub fn identifier<'a>() -> Parser<'a, String> {
+pub fn word<'a>() -> Parser<'a, String> {
     strip(letter().and(alphanumeric().many()).map(|(first, rest)| {
         let mut result = String::new();
         result.push(first);
         result.push_str(&rest.iter().collect::<String>());
         result
     }))
+    .label("identifier")
+}
+
+pub fn identifier_excluding<'a>(reserved: &'a [&'a str]) -> Parser<'a, String> {
+    let word = word();
+
+    Parser::new(move |input: Input<'a>| {
+        let (name, remaining) = word.run(input)?;
+        if !reserved.contains(&name.as_str()) {
+            return Ok((name, remaining));
+        }
+
+        let start = trivia().run(input).map_or(input, |(_, rest)| rest);
+        Err(ParseError {
+            found: Some(format!("keyword {}", quote(&name))),
+            ..ParseError::expected(start, "identifier")
+        })
+    })
 }
 
 pub fn symbol<'a>(a: &'a str) -> Parser<'a, String> {
     strip(string(a))
 }
 
+pub fn keyword<'a>(a: &'a str) -> Parser<'a, String> {
+    let boundary = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    strip(Parser::new(move |input: Input<'a>| {
+        match input.rest().strip_prefix(a) {
+            Some(rest) if !rest.starts_with(boundary) => {
+                Ok((a.to_string(), input.advance(a.len())))
+            }
+            _ => Err(ParseError::expected(input, quote(a))),
+        }
+    }))
+}
+
 pub fn tuple<'a, T>(a: Parser<'a, T>) -> Parser<'a, T> {
     between(symbol("("), a, symbol(")"))
 }
@@ -237,24 +692,202 @@ pub fn block<'a, T>(a: Parser<'a, T>) -> Parser<'a, T> {
     between(symbol("{"), a, symbol("}"))
 }
 
-pub fn integer<'a>() -> Parser<'a, String> {
-    strip(digit().some().map(|c| c.into_iter().collect()))
-}
-
-pub fn float<'a>() -> Parser<'a, String> {
-    strip(
-        digit()
-            .some()
-            .qualify()
-            .and(symbol("."))
-            .and(digit().many().qualify())
-            .left(symbol("f").many())
-            .map(|((a, b), c)| {
-                let mut result = String::new();
-                result.push_str(a.as_str());
-                result.push_str(b.as_str());
-                result.push_str(c.as_str());
-                result
-            }),
+pub fn digits<'a>(digit: Parser<'a, char>) -> Parser<'a, String> {
+    digit
+        .clone()
+        .and(digit.or(char('_')).many())
+        .map(|(first, rest)| {
+            let mut result = String::new();
+            result.push(first);
+            result.extend(rest.into_iter().filter(|c| *c != '_'));
+            result
+        })
+}
+
+fn radix<'a>(prefix: &'a str, digit: Parser<'a, char>) -> Parser<'a, String> {
+    string(prefix)
+        .right(char('_').many().right(digits(digit)).commit())
+        .map(move |digits| format!("{}{}", prefix, digits))
+}
+
+pub fn integer_text<'a>() -> Parser<'a, String> {
+    radix("0x", hex_digit())
+        .or(radix("0o", octal_digit()))
+        .or(radix("0b", binary_digit()))
+        .or(digits(digit()))
+}
+
+pub fn float_text<'a>() -> Parser<'a, String> {
+    let fraction = char('.')
+        .right(digits(digit()))
+        .map(|digits| format!(".{}", digits));
+    let exponent = list(&['e', 'E'])
+        .right(otherwise(list(&['+', '-']).map(Some), None))
+        .and(digits(digit()))
+        .map(|(sign, digits)| match sign {
+            Some(sign) => format!("e{}{}", sign, digits),
+            None => format!("e{}", digits),
+        });
+
+    digits(digit())
+        .and(
+            fraction
+                .and(otherwise(exponent.clone(), String::new()))
+                .map(|(fraction, exponent)| fraction + &exponent)
+                .or(exponent),
+        )
+        .map(|(whole, rest)| whole + &rest)
+}
+
+pub fn suffix<'a>() -> Parser<'a, Option<String>> {
+    otherwise(
+        letter().and(alphanumeric().many()).map(|(first, rest)| {
+            let mut result = String::new();
+            result.push(first);
+            result.extend(rest);
+            Some(result)
+        }),
+        None,
     )
 }
+
+pub fn escape<'a>() -> Parser<'a, char> {
+    let simple = list(&['n', 't', 'r', '0', '\\', '"', '\'']).map(|c| match c {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        '0' => '\0',
+        c => c,
+    });
+
+    let hex = char('x')
+        .right(hex_digit().and(hex_digit()))
+        .try_map(|(high, low)| {
+            let value = high.to_digit(16).unwrap() * 16 + low.to_digit(16).unwrap();
+            match value {
+                0..=0x7f => Ok(value as u8 as char),
+                _ => Err(format!("hex escape `\\x{}{}` is out of range", high, low)),
+            }
+        });
+
+    let unicode = char('u')
+        .right(between(char('{'), hex_digit().some().qualify(), char('}')))
+        .try_map(|digits| {
+            u32::from_str_radix(&digits, 16)
+                .ok()
+                .filter(|_| digits.len() <= 6)
+                .and_then(std::char::from_u32)
+                .ok_or_else(|| format!("invalid unicode escape `\\u{{{}}}`", digits))
+        });
+
+    char('\\').right(simple.or(hex).or(unicode).label("escape sequence").commit())
+}
+
+pub fn quoted_string<'a>() -> Parser<'a, String> {
+    let contents = escape().or(none_of(&['"', '\\'])).many().qualify();
+
+    strip(char('"').right(contents.left(char('"')).commit())).label("string")
+}
+
+pub fn quoted_char<'a>() -> Parser<'a, char> {
+    let contents = escape().or(none_of(&['\'', '\\', '\n']).label("character"));
+
+    strip(char('\'').right(contents.left(char('\'')).commit())).label("character")
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Associativity {
+    Left,
+    #[allow(dead_code)]
+    Right,
+    None,
+}
+
+pub struct Infix<'a, O> {
+    pub operator: Parser<'a, O>,
+    pub precedence: u8,
+    pub associativity: Associativity,
+}
+
+pub fn infix<'a, O>(
+    operator: Parser<'a, O>,
+    precedence: u8,
+    associativity: Associativity,
+) -> Infix<'a, O> {
+    Infix {
+        operator,
+        precedence,
+        associativity,
+    }
+}
+
+pub fn climb<'a, T, O, F>(
+    operand: Parser<'a, T>,
+    table: Vec<Infix<'a, O>>,
+    combine: F,
+) -> Parser<'a, T>
+where
+    T: 'a,
+    O: 'a,
+    F: 'a + Fn(O, T, T) -> T,
+{
+    Parser::new(move |input: Input<'a>| climb_from(&operand, &table, &combine, input, 0))
+}
+
+fn climb_from<'a, T, O, F>(
+    operand: &Parser<'a, T>,
+    table: &[Infix<'a, O>],
+    combine: &F,
+    input: Input<'a>,
+    minimum: u8,
+) -> ParseResult<'a, T>
+where
+    F: Fn(O, T, T) -> T,
+{
+    let (mut left, mut input) = operand.run(input)?;
+    let mut chained = None;
+
+    loop {
+        let matched = table
+            .iter()
+            .filter_map(|infix| {
+                infix
+                    .operator
+                    .run(input)
+                    .ok()
+                    .map(|(operator, remaining)| (infix, operator, remaining))
+            })
+            .reduce(|longest, candidate| {
+                if candidate.2.position().offset > longest.2.position().offset {
+                    candidate
+                } else {
+                    longest
+                }
+            });
+
+        let (infix, operator, remaining) = match matched {
+            Some(matched) if matched.0.precedence >= minimum => matched,
+            _ => return Ok((left, input)),
+        };
+
+        if infix.associativity == Associativity::None && chained == Some(infix.precedence) {
+            return Err(ParseError {
+                committed: true,
+                ..ParseError::message(input, "non-associative operators cannot be chained")
+            });
+        }
+
+        let next = match infix.associativity {
+            Associativity::Left | Associativity::None => infix.precedence + 1,
+            Associativity::Right => infix.precedence,
+        };
+
+        let (right, remaining) = climb_from(operand, table, combine, remaining, next)?;
+        left = combine(operator, left, right);
+        input = remaining;
+        chained = match infix.associativity {
+            Associativity::None => Some(infix.precedence),
+            _ => None,
+        };
+    }
+}
@@ -1,31 +1,331 @@
+use crate::parser::Spanned;
+
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub enum Type {
     Atomic(String),
-    Array(Box<Type>),
+    Array(Box<Spanned<Type>>),
+    Tuple(Vec<Spanned<Type>>),
+    Generic(String, Vec<Spanned<Type>>),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum IntegerSuffix {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl IntegerSuffix {
+    pub fn from_name(name: &str) -> Option<Self> {
+        use IntegerSuffix::*;
+
+        match name {
+            "i8" => Some(I8),
+            "i16" => Some(I16),
+            "i32" => Some(I32),
+            "i64" => Some(I64),
+            "i128" => Some(I128),
+            "isize" => Some(Isize),
+            "u8" => Some(U8),
+            "u16" => Some(U16),
+            "u32" => Some(U32),
+            "u64" => Some(U64),
+            "u128" => Some(U128),
+            "usize" => Some(Usize),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        use IntegerSuffix::*;
+
+        match self {
+            I8 => "i8",
+            I16 => "i16",
+            I32 => "i32",
+            I64 => "i64",
+            I128 => "i128",
+            Isize => "isize",
+            U8 => "u8",
+            U16 => "u16",
+            U32 => "u32",
+            U64 => "u64",
+            U128 => "u128",
+            Usize => "usize",
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        use IntegerSuffix::*;
+
+        match self {
+            I8 | U8 => 8,
+            I16 | U16 => 16,
+            I32 | U32 => 32,
+            I64 | U64 | Isize | Usize => 64,
+            I128 | U128 => 128,
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        use IntegerSuffix::*;
+
+        matches!(self, I8 | I16 | I32 | I64 | I128 | Isize)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum FloatSuffix {
+    F32,
+    F64,
+}
+
+impl FloatSuffix {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "f32" => Some(FloatSuffix::F32),
+            "f64" => Some(FloatSuffix::F64),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FloatSuffix::F32 => "f32",
+            FloatSuffix::F64 => "f64",
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub enum Literal {
-    Float(String),
-    Integer(String),
+    Float(String, Option<FloatSuffix>),
+    Integer(String, Option<IntegerSuffix>),
     Boolean(bool),
+    String(String),
+    Char(char),
     Reference(String),
 }
 
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    And,
+    Or,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum UnaryOperator {
+    Negate,
+    Not,
+    BitNot,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum ArrayElements {
+    List(Vec<Spanned<Expression>>),
+    Repeat(Box<Spanned<Expression>>, Box<Spanned<Expression>>),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct FieldValue {
+    pub name: String,
+    pub value: Spanned<Expression>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct FieldPattern {
+    pub name: String,
+    pub pattern: Option<Spanned<Pattern>>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum VariantPatterns {
+    Unit,
+    Tuple(Vec<Spanned<Pattern>>),
+    Struct(Vec<Spanned<FieldPattern>>),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Pattern {
+    Wildcard,
+    Binding(String),
+    Literal(Spanned<Literal>),
+    Negative(Spanned<Literal>),
+    Tuple(Vec<Spanned<Pattern>>),
+    Struct {
+        name: String,
+        fields: Vec<Spanned<FieldPattern>>,
+    },
+    Variant {
+        name: String,
+        variant: String,
+        values: VariantPatterns,
+    },
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Spanned<Pattern>,
+    pub guard: Option<Spanned<Expression>>,
+    pub body: Spanned<Expression>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum VariantValues {
+    Unit,
+    Tuple(Vec<Spanned<Expression>>),
+    Struct(Vec<Spanned<FieldValue>>),
+}
+
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub enum Expression {
-    Block(Vec<Statement>),
-    Literal(Literal),
+    Block(Vec<Spanned<Statement>>),
+    Literal(Spanned<Literal>),
+    If {
+        condition: Box<Spanned<Expression>>,
+        then_branch: Box<Spanned<Expression>>,
+        else_branch: Option<Box<Spanned<Expression>>>,
+    },
+    Loop(Box<Spanned<Expression>>),
+    Match {
+        scrutinee: Box<Spanned<Expression>>,
+        arms: Vec<Spanned<MatchArm>>,
+    },
+    Array(ArrayElements),
+    Tuple(Vec<Spanned<Expression>>),
+    Index {
+        target: Box<Spanned<Expression>>,
+        index: Box<Spanned<Expression>>,
+    },
+    Field {
+        target: Box<Spanned<Expression>>,
+        field: String,
+    },
+    Element {
+        target: Box<Spanned<Expression>>,
+        index: usize,
+    },
+    Struct {
+        name: String,
+        fields: Vec<Spanned<FieldValue>>,
+    },
+    Variant {
+        name: String,
+        variant: String,
+        values: VariantValues,
+    },
+    Call {
+        callee: Box<Spanned<Expression>>,
+        args: Vec<Spanned<Expression>>,
+    },
+    MethodCall {
+        receiver: Box<Spanned<Expression>>,
+        method: String,
+        args: Vec<Spanned<Expression>>,
+    },
+    Unary(UnaryOperator, Box<Spanned<Expression>>),
+    Binary(
+        BinaryOperator,
+        Box<Spanned<Expression>>,
+        Box<Spanned<Expression>>,
+    ),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct GenericParameter {
+    pub name: String,
+    pub bounds: Vec<Spanned<Type>>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Parameter {
+    pub name: String,
+    pub annotation: Spanned<Type>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct StructField {
+    pub name: String,
+    pub annotation: Spanned<Type>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum VariantFields {
+    Unit,
+    Tuple(Vec<Spanned<Type>>),
+    Struct(Vec<Spanned<StructField>>),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct EnumVariant {
+    pub name: String,
+    pub fields: VariantFields,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub enum Statement {
-    Declare(Option<Type>, String),
-    Assign(String, Expression),
-    Instantiate(Option<Type>, String, Expression),
-    Expression(Expression),
+    Declare(Option<Spanned<Type>>, String),
+    Assign(
+        Spanned<Expression>,
+        Option<BinaryOperator>,
+        Spanned<Expression>,
+    ),
+    Instantiate(Option<Spanned<Type>>, Spanned<Pattern>, Spanned<Expression>),
+    Function {
+        name: String,
+        generics: Vec<Spanned<GenericParameter>>,
+        parameters: Vec<Spanned<Parameter>>,
+        output: Option<Spanned<Type>>,
+        body: Spanned<Expression>,
+    },
+    Struct {
+        name: String,
+        generics: Vec<Spanned<GenericParameter>>,
+        fields: Vec<Spanned<StructField>>,
+    },
+    Enum {
+        name: String,
+        variants: Vec<Spanned<EnumVariant>>,
+    },
+    While {
+        condition: Spanned<Expression>,
+        body: Spanned<Expression>,
+    },
+    For {
+        binding: String,
+        iterable: Spanned<Expression>,
+        body: Spanned<Expression>,
+    },
+    Break(Option<Spanned<Expression>>),
+    Continue,
+    Expression(Spanned<Expression>),
     NoOp,
 }
 
 #[derive(PartialEq, Eq, Debug)]
-pub struct Program(pub Vec<Statement>);
+pub struct Program(pub Vec<Spanned<Statement>>);
@@ -0,0 +1,31 @@
+use std::fmt;
+
+use super::prelude::*;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Diagnostics<'a> {
+    pub source: &'a str,
+    pub errors: Vec<ParseError>,
+}
+
+impl<'a> Diagnostics<'a> {
+    pub fn new(source: &'a str, errors: Vec<ParseError>) -> Self {
+        Diagnostics { source, errors }
+    }
+}
+
+impl fmt::Display for Diagnostics<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for error in &self.errors {
+            let position = error.position;
+            let line = self.source.lines().nth(position.line - 1).unwrap_or("");
+            let gutter = " ".repeat(position.line.to_string().len());
+
+            writeln!(f, "error: {}", error)?;
+            writeln!(f, "{} |", gutter)?;
+            writeln!(f, "{} | {}", position.line, line)?;
+            writeln!(f, "{} | {}^", gutter, " ".repeat(position.column - 1))?;
+        }
+        Ok(())
+    }
+}
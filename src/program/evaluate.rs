@@ -0,0 +1,354 @@
+use std::collections::BTreeMap;
+
+use super::prelude::*;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Number {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    F64(f64),
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct LiteralError {
+    pub span: Span,
+    pub message: String,
+}
+
+pub type Literals = BTreeMap<Span, Number>;
+
+#[derive(Default)]
+struct Evaluation {
+    values: Literals,
+    errors: Vec<LiteralError>,
+}
+
+impl Evaluation {
+    fn record(&mut self, span: Span, result: Option<Result<Number, String>>) {
+        match result {
+            Some(Ok(value)) => {
+                self.values.insert(span, value);
+            }
+            Some(Err(message)) => self.errors.push(LiteralError { span, message }),
+            None => {}
+        }
+    }
+}
+
+impl LiteralError {
+    pub fn locate(&self, source: &str) -> ParseError {
+        ParseError::message(
+            Input::new(source).skip(self.span.start),
+            self.message.clone(),
+        )
+    }
+}
+
+fn split_radix(text: &str) -> (&str, u32) {
+    match text.get(..2) {
+        Some("0x") => (&text[2..], 16),
+        Some("0o") => (&text[2..], 8),
+        Some("0b") => (&text[2..], 2),
+        _ => (text, 10),
+    }
+}
+
+pub fn integer_value(text: &str, negative: bool, kind: IntegerSuffix) -> Result<Number, String> {
+    let out_of_range = || {
+        let sign = if negative { "-" } else { "" };
+        format!(
+            "integer literal `{}{}` is out of range for `{}`",
+            sign,
+            text,
+            kind.name()
+        )
+    };
+
+    let (digits, radix) = split_radix(text);
+    let digits = digits.replace('_', "");
+    let magnitude = u128::from_str_radix(&digits, radix).map_err(|_| out_of_range())?;
+
+    let bits = kind.bits();
+    let limit = match (kind.is_signed(), negative) {
+        (true, true) => 1u128 << (bits - 1),
+        (true, false) => (1u128 << (bits - 1)) - 1,
+        (false, true) => 0,
+        (false, false) => u128::MAX >> (128 - bits),
+    };
+    if magnitude > limit {
+        return Err(out_of_range());
+    }
+
+    Ok(match (kind, negative) {
+        (IntegerSuffix::I128, true) => Number::I128((magnitude as i128).wrapping_neg()),
+        (IntegerSuffix::I128, false) => Number::I128(magnitude as i128),
+        (IntegerSuffix::U128, _) => Number::U128(magnitude),
+        (_, true) => Number::I64((magnitude as i64).wrapping_neg()),
+        (kind, false) if kind.is_signed() => Number::I64(magnitude as i64),
+        (_, false) => Number::U64(magnitude as u64),
+    })
+}
+
+pub fn float_value(text: &str, negative: bool, kind: FloatSuffix) -> Result<Number, String> {
+    let digits = text.replace('_', "");
+    let value = match kind {
+        FloatSuffix::F32 => digits.parse::<f32>().map(f64::from),
+        FloatSuffix::F64 => digits.parse::<f64>(),
+    }
+    .ok()
+    .filter(|value| value.is_finite())
+    .ok_or_else(|| {
+        format!(
+            "float literal `{}` is out of range for `{}`",
+            text,
+            kind.name()
+        )
+    })?;
+
+    Ok(Number::F64(if negative { -value } else { value }))
+}
+
+pub fn literal_value(
+    literal: &Literal,
+    negative: bool,
+    expected: Option<&Type>,
+) -> Option<Result<Number, String>> {
+    let expected = match expected {
+        Some(Type::Atomic(name)) => Some(name.as_str()),
+        _ => None,
+    };
+
+    match literal {
+        Literal::Integer(text, suffix) => {
+            match suffix.or_else(|| expected.and_then(IntegerSuffix::from_name)) {
+                Some(kind) => Some(integer_value(text, negative, kind)),
+                None => {
+                    let widest = if negative {
+                        IntegerSuffix::I128
+                    } else {
+                        IntegerSuffix::U128
+                    };
+                    Some(
+                        integer_value(text, negative, IntegerSuffix::I64)
+                            .or_else(|_| integer_value(text, negative, widest)),
+                    )
+                }
+            }
+        }
+        Literal::Float(text, suffix) => {
+            let kind = suffix
+                .or_else(|| expected.and_then(FloatSuffix::from_name))
+                .unwrap_or(FloatSuffix::F64);
+            Some(float_value(text, negative, kind))
+        }
+        _ => None,
+    }
+}
+
+fn evaluate_pattern(pattern: &Spanned<Pattern>, evaluation: &mut Evaluation) {
+    match &pattern.node {
+        Pattern::Literal(literal) => {
+            evaluation.record(pattern.span, literal_value(&literal.node, false, None))
+        }
+        Pattern::Negative(literal) => {
+            evaluation.record(pattern.span, literal_value(&literal.node, true, None))
+        }
+        Pattern::Tuple(patterns) => {
+            for pattern in patterns {
+                evaluate_pattern(pattern, evaluation);
+            }
+        }
+        Pattern::Struct { fields, .. }
+        | Pattern::Variant {
+            values: VariantPatterns::Struct(fields),
+            ..
+        } => {
+            for pattern in fields
+                .iter()
+                .filter_map(|field| field.node.pattern.as_ref())
+            {
+                evaluate_pattern(pattern, evaluation);
+            }
+        }
+        Pattern::Variant {
+            values: VariantPatterns::Tuple(patterns),
+            ..
+        } => {
+            for pattern in patterns {
+                evaluate_pattern(pattern, evaluation);
+            }
+        }
+        Pattern::Wildcard
+        | Pattern::Binding(_)
+        | Pattern::Variant {
+            values: VariantPatterns::Unit,
+            ..
+        } => {}
+    }
+}
+
+fn evaluate_expression(
+    expression: &Spanned<Expression>,
+    expected: Option<&Type>,
+    evaluation: &mut Evaluation,
+) {
+    match &expression.node {
+        Expression::Literal(literal) => evaluation.record(
+            expression.span,
+            literal_value(&literal.node, false, expected),
+        ),
+        Expression::Unary(UnaryOperator::Negate, operand) => match &operand.node {
+            Expression::Literal(literal) => evaluation.record(
+                expression.span,
+                literal_value(&literal.node, true, expected),
+            ),
+            _ => evaluate_expression(operand, expected, evaluation),
+        },
+        Expression::Unary(_, operand) => evaluate_expression(operand, expected, evaluation),
+        Expression::Binary(operator, left, right) => {
+            use BinaryOperator::*;
+
+            let (left_type, right_type) = match operator {
+                Add | Subtract | Multiply | Divide | Remainder | BitAnd | BitOr | BitXor => {
+                    (expected, expected)
+                }
+                ShiftLeft | ShiftRight => (expected, None),
+                _ => (None, None),
+            };
+            evaluate_expression(left, left_type, evaluation);
+            evaluate_expression(right, right_type, evaluation);
+        }
+        Expression::Block(statements) => evaluate_statements(statements, evaluation),
+        Expression::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            evaluate_expression(condition, None, evaluation);
+            evaluate_expression(then_branch, None, evaluation);
+            if let Some(else_branch) = else_branch {
+                evaluate_expression(else_branch, None, evaluation);
+            }
+        }
+        Expression::Loop(body) => evaluate_expression(body, None, evaluation),
+        Expression::Match { scrutinee, arms } => {
+            evaluate_expression(scrutinee, None, evaluation);
+            for arm in arms {
+                evaluate_pattern(&arm.node.pattern, evaluation);
+                if let Some(guard) = &arm.node.guard {
+                    evaluate_expression(guard, None, evaluation);
+                }
+                evaluate_expression(&arm.node.body, None, evaluation);
+            }
+        }
+        Expression::Array(elements) => {
+            let element = match expected {
+                Some(Type::Array(element)) => Some(&element.node),
+                _ => None,
+            };
+            match elements {
+                ArrayElements::List(values) => {
+                    for value in values {
+                        evaluate_expression(value, element, evaluation);
+                    }
+                }
+                ArrayElements::Repeat(value, count) => {
+                    evaluate_expression(value, element, evaluation);
+                    evaluate_expression(count, None, evaluation);
+                }
+            }
+        }
+        Expression::Struct { fields, .. } => {
+            for field in fields {
+                evaluate_expression(&field.node.value, None, evaluation);
+            }
+        }
+        Expression::Variant { values, .. } => match values {
+            VariantValues::Unit => {}
+            VariantValues::Tuple(values) => {
+                for value in values {
+                    evaluate_expression(value, None, evaluation);
+                }
+            }
+            VariantValues::Struct(fields) => {
+                for field in fields {
+                    evaluate_expression(&field.node.value, None, evaluation);
+                }
+            }
+        },
+        Expression::Tuple(values) => {
+            for (position, value) in values.iter().enumerate() {
+                let element = match expected {
+                    Some(Type::Tuple(elements)) => elements.get(position).map(|e| &e.node),
+                    _ => None,
+                };
+                evaluate_expression(value, element, evaluation);
+            }
+        }
+        Expression::Element { target, .. } => evaluate_expression(target, None, evaluation),
+        Expression::Field { target, .. } => evaluate_expression(target, None, evaluation),
+        Expression::Index { target, index } => {
+            evaluate_expression(target, None, evaluation);
+            evaluate_expression(index, None, evaluation);
+        }
+        Expression::Call { callee, args } => {
+            evaluate_expression(callee, None, evaluation);
+            for arg in args {
+                evaluate_expression(arg, None, evaluation);
+            }
+        }
+        Expression::MethodCall { receiver, args, .. } => {
+            evaluate_expression(receiver, None, evaluation);
+            for arg in args {
+                evaluate_expression(arg, None, evaluation);
+            }
+        }
+    }
+}
+
+fn evaluate_statements(statements: &[Spanned<Statement>], evaluation: &mut Evaluation) {
+    for statement in statements {
+        match &statement.node {
+            Statement::Instantiate(annotation, _, value) => evaluate_expression(
+                value,
+                annotation.as_ref().map(|annotation| &annotation.node),
+                evaluation,
+            ),
+            Statement::Assign(target, _, value) => {
+                evaluate_expression(target, None, evaluation);
+                evaluate_expression(value, None, evaluation);
+            }
+            Statement::Expression(value) => evaluate_expression(value, None, evaluation),
+            Statement::Function { output, body, .. } => {
+                evaluate_expression(body, output.as_ref().map(|output| &output.node), evaluation)
+            }
+            Statement::While { condition, body } => {
+                evaluate_expression(condition, None, evaluation);
+                evaluate_expression(body, None, evaluation);
+            }
+            Statement::For { iterable, body, .. } => {
+                evaluate_expression(iterable, None, evaluation);
+                evaluate_expression(body, None, evaluation);
+            }
+            Statement::Break(Some(value)) => evaluate_expression(value, None, evaluation),
+            Statement::Declare(..)
+            | Statement::Struct { .. }
+            | Statement::Enum { .. }
+            | Statement::Break(None)
+            | Statement::Continue => {}
+            Statement::NoOp => {}
+        }
+    }
+}
+
+pub fn evaluate_literals(program: &Program) -> Result<Literals, Vec<LiteralError>> {
+    let mut evaluation = Evaluation::default();
+    evaluate_statements(&program.0, &mut evaluation);
+    if evaluation.errors.is_empty() {
+        Ok(evaluation.values)
+    } else {
+        Err(evaluation.errors)
+    }
+}
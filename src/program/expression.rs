@@ -1,13 +1,338 @@
 use super::prelude::*;
 
 pub fn literal_expr<'a>() -> Parser<'a, Expression> {
-    literal().map(|x| Expression::Literal(x))
+    literal().map(Expression::Literal)
 }
 
 pub fn block_expr<'a>() -> Parser<'a, Expression> {
-    block(statement().left(semicolon()).many()).map(|x| Expression::Block(x))
+    symbol("{")
+        .right(statement().left(semicolon()).many_till(symbol("}")))
+        .map(Expression::Block)
 }
 
-pub fn expression<'a>() -> Parser<'a, Expression> {
-    Parser::lazy(|| literal_expr().or(block_expr()))
+pub fn if_expr<'a>() -> Parser<'a, Expression> {
+    Parser::lazy(|| {
+        if_key()
+            .right(condition().commit())
+            .and(spanned(block_expr()).commit())
+            .and(otherwise(
+                else_key()
+                    .right(spanned(if_expr().or(block_expr())).commit())
+                    .map(Some),
+                None,
+            ))
+            .map(|((condition, then_branch), else_branch)| Expression::If {
+                condition: Box::new(condition),
+                then_branch: Box::new(then_branch),
+                else_branch: else_branch.map(Box::new),
+            })
+    })
+}
+
+pub fn loop_expr<'a>() -> Parser<'a, Expression> {
+    loop_key()
+        .right(spanned(block_expr()).commit())
+        .map(|body| Expression::Loop(Box::new(body)))
+}
+
+pub fn match_arm<'a>() -> Parser<'a, MatchArm> {
+    pattern()
+        .and(otherwise(
+            if_key().right(expression().commit()).map(Some),
+            None,
+        ))
+        .left(fat_arrow().commit())
+        .and(expression().commit())
+        .map(|((pattern, guard), body)| MatchArm {
+            pattern,
+            guard,
+            body,
+        })
+}
+
+fn is_block_like(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::Block(_)
+            | Expression::If { .. }
+            | Expression::Loop(_)
+            | Expression::Match { .. }
+    )
+}
+
+pub fn match_arms<'a>() -> Parser<'a, Vec<Spanned<MatchArm>>> {
+    let arm = spanned(match_arm());
+    let comma = comma();
+    let close = lookahead(symbol("}"));
+
+    let separated_arm = Parser::new(move |input: Input<'a>| {
+        let (arm, remaining) = arm.run(input)?;
+        match comma.run(remaining) {
+            Ok((_, remaining)) => Ok((arm, remaining)),
+            Err(_) if is_block_like(&arm.node.body.node) => Ok((arm, remaining)),
+            Err(error) => close
+                .run(remaining)
+                .map(|_| (arm, remaining))
+                .map_err(|stop| error.merge(stop)),
+        }
+    });
+
+    symbol("{").right(separated_arm.many_till(symbol("}")))
+}
+
+pub fn match_expr<'a>() -> Parser<'a, Expression> {
+    match_key()
+        .right(condition().commit())
+        .and(match_arms().commit())
+        .map(|(scrutinee, arms)| Expression::Match {
+            scrutinee: Box::new(scrutinee),
+            arms,
+        })
+}
+
+#[derive(Clone)]
+enum ArrayTail {
+    Repeat(Spanned<Expression>),
+    List(Vec<Spanned<Expression>>),
+}
+
+pub fn array_expr<'a>() -> Parser<'a, Expression> {
+    let tail = semicolon()
+        .right(expression().commit())
+        .map(ArrayTail::Repeat)
+        .or(comma()
+            .right(comma_list(expression(), "]").commit())
+            .map(|(rest, _)| ArrayTail::List(rest)));
+    let tail = otherwise(tail, ArrayTail::List(vec![]));
+    let elements = expression().and(tail).map(|(first, tail)| match tail {
+        ArrayTail::Repeat(count) => ArrayElements::Repeat(Box::new(first), Box::new(count)),
+        ArrayTail::List(mut rest) => {
+            rest.insert(0, first);
+            ArrayElements::List(rest)
+        }
+    });
+
+    set(otherwise(elements, ArrayElements::List(vec![]))).map(Expression::Array)
+}
+
+pub fn field_value<'a>() -> Parser<'a, FieldValue> {
+    identifier()
+        .left(colon())
+        .and(expression().commit())
+        .map(|(name, value)| FieldValue { name, value })
+}
+
+pub fn struct_expr<'a>() -> Parser<'a, Expression> {
+    identifier()
+        .and(fields(spanned(field_value())))
+        .map(|(name, fields)| Expression::Struct { name, fields })
+}
+
+pub fn variant_expr<'a>(structs: bool) -> Parser<'a, Expression> {
+    let mut values = arguments().map(VariantValues::Tuple);
+    if structs {
+        values = values.or(fields(spanned(field_value())).map(VariantValues::Struct));
+    }
+
+    identifier()
+        .left(path_separator())
+        .and(identifier().commit())
+        .and(otherwise(values, VariantValues::Unit))
+        .map(|((name, variant), values)| Expression::Variant {
+            name,
+            variant,
+            values,
+        })
+}
+
+pub fn group_expr<'a>() -> Parser<'a, Spanned<Expression>> {
+    parenthesized(expression(), Expression::Tuple)
+}
+
+pub fn primary<'a>(structs: bool) -> Parser<'a, Spanned<Expression>> {
+    let literal = if structs {
+        struct_expr().or(literal_expr())
+    } else {
+        literal_expr()
+    };
+
+    spanned(
+        if_expr()
+            .or(loop_expr())
+            .or(match_expr())
+            .or(variant_expr(structs))
+            .or(literal)
+            .or(array_expr())
+            .or(block_expr()),
+    )
+    .or(group_expr())
+}
+
+enum Postfix {
+    Call(Vec<Spanned<Expression>>),
+    Method(String, Vec<Spanned<Expression>>),
+    Index(Spanned<Expression>),
+    Field(String),
+    Element(usize),
+}
+
+pub fn arguments<'a>() -> Parser<'a, Vec<Spanned<Expression>>> {
+    elements(expression())
+}
+
+fn tuple_index<'a>() -> Parser<'a, usize> {
+    digit()
+        .and(digit().or(char('_')).many())
+        .map(|(first, rest)| {
+            let mut index = String::from(first);
+            index.extend(rest);
+            index
+        })
+        .validate(|index| {
+            let canonical = index == "0" || !index.starts_with('0');
+            match index.parse() {
+                Ok(value) if canonical => Ok(value),
+                _ => Err(format!("invalid tuple index `{}`", index)),
+            }
+        })
+}
+
+fn postfix<'a>() -> Parser<'a, Spanned<Postfix>> {
+    spanned(
+        arguments()
+            .map(Postfix::Call)
+            .or(symbol(".")
+                .right(identifier())
+                .and(arguments())
+                .map(|(method, args)| Postfix::Method(method, args)))
+            .or(symbol(".").right(identifier()).map(Postfix::Field))
+            .or(symbol(".")
+                .right(strip(tuple_index()))
+                .map(Postfix::Element))
+            .or(set(expression()).map(Postfix::Index)),
+    )
+}
+
+pub fn postfix_expr<'a>(structs: bool) -> Parser<'a, Spanned<Expression>> {
+    primary(structs)
+        .and(postfix().many())
+        .map(|(first, suffixes)| {
+            suffixes.into_iter().fold(first, |target, suffix| {
+                let span = Span::new(target.span.start, suffix.span.end);
+                let node = match suffix.node {
+                    Postfix::Call(args) => Expression::Call {
+                        callee: Box::new(target),
+                        args,
+                    },
+                    Postfix::Method(method, args) => Expression::MethodCall {
+                        receiver: Box::new(target),
+                        method,
+                        args,
+                    },
+                    Postfix::Index(index) => Expression::Index {
+                        target: Box::new(target),
+                        index: Box::new(index),
+                    },
+                    Postfix::Field(field) => Expression::Field {
+                        target: Box::new(target),
+                        field,
+                    },
+                    Postfix::Element(index) => Expression::Element {
+                        target: Box::new(target),
+                        index,
+                    },
+                };
+                Spanned::new(node, span)
+            })
+        })
+}
+
+pub fn unary_operator<'a>() -> Parser<'a, UnaryOperator> {
+    symbol("-")
+        .right(Parser::pure(UnaryOperator::Negate))
+        .or(symbol("!").right(Parser::pure(UnaryOperator::Not)))
+        .or(symbol("~").right(Parser::pure(UnaryOperator::BitNot)))
+}
+
+pub fn unary_expr<'a>(structs: bool) -> Parser<'a, Spanned<Expression>> {
+    Parser::lazy(move || {
+        spanned(
+            unary_operator()
+                .and(unary_expr(structs))
+                .map(|(operator, operand)| Expression::Unary(operator, Box::new(operand))),
+        )
+        .or(postfix_expr(structs))
+    })
+}
+
+pub fn binary_operator<'a>(a: &'a str, operator: BinaryOperator) -> Parser<'a, BinaryOperator> {
+    symbol(a).right(Parser::pure(operator))
+}
+
+pub fn assign_operator<'a>() -> Parser<'a, Option<BinaryOperator>> {
+    use BinaryOperator::*;
+
+    equals()
+        .right(Parser::pure(None))
+        .or(binary_operator("+=", Add)
+            .or(binary_operator("-=", Subtract))
+            .or(binary_operator("*=", Multiply))
+            .or(binary_operator("/=", Divide))
+            .or(binary_operator("%=", Remainder))
+            .or(binary_operator("<<=", ShiftLeft))
+            .or(binary_operator(">>=", ShiftRight))
+            .or(binary_operator("&=", BitAnd))
+            .or(binary_operator("|=", BitOr))
+            .or(binary_operator("^=", BitXor))
+            .map(Some))
+}
+
+pub fn binary_operators<'a>() -> Vec<Infix<'a, BinaryOperator>> {
+    use Associativity::Left;
+    use BinaryOperator::*;
+
+    let binary_operator = |a, operator| not(assign_operator()).right(binary_operator(a, operator));
+
+    vec![
+        infix(binary_operator("||", Or), 1, Left),
+        infix(binary_operator("&&", And), 2, Left),
+        infix(binary_operator("==", Equal), 3, Associativity::None),
+        infix(binary_operator("!=", NotEqual), 3, Associativity::None),
+        infix(binary_operator("<", Less), 3, Associativity::None),
+        infix(binary_operator(">", Greater), 3, Associativity::None),
+        infix(binary_operator("<=", LessEqual), 3, Associativity::None),
+        infix(binary_operator(">=", GreaterEqual), 3, Associativity::None),
+        infix(binary_operator("|", BitOr), 4, Left),
+        infix(binary_operator("^", BitXor), 5, Left),
+        infix(binary_operator("&", BitAnd), 6, Left),
+        infix(binary_operator("<<", ShiftLeft), 7, Left),
+        infix(binary_operator(">>", ShiftRight), 7, Left),
+        infix(binary_operator("+", Add), 8, Left),
+        infix(binary_operator("-", Subtract), 8, Left),
+        infix(binary_operator("*", Multiply), 9, Left),
+        infix(binary_operator("/", Divide), 9, Left),
+        infix(binary_operator("%", Remainder), 9, Left),
+    ]
+}
+
+pub fn binary_expr<'a>(structs: bool) -> Parser<'a, Spanned<Expression>> {
+    climb(
+        unary_expr(structs).label("expression"),
+        binary_operators(),
+        |operator, left, right| {
+            let span = Span::new(left.span.start, right.span.end);
+            Spanned::new(
+                Expression::Binary(operator, Box::new(left), Box::new(right)),
+                span,
+            )
+        },
+    )
+}
+
+pub fn expression<'a>() -> Parser<'a, Spanned<Expression>> {
+    Parser::lazy(|| binary_expr(true).label("expression"))
+}
+
+pub fn condition<'a>() -> Parser<'a, Spanned<Expression>> {
+    Parser::lazy(|| binary_expr(false).label("expression"))
 }
@@ -1,11 +1,46 @@
 use super::prelude::*;
 
+fn typed_suffix<'a, T, F>(kind: &'static str, from_name: F) -> Parser<'a, Option<T>>
+where
+    T: 'a,
+    F: 'a + Fn(&str) -> Option<T>,
+{
+    suffix()
+        .try_map(move |suffix| match suffix {
+            Some(name) => from_name(&name)
+                .map(Some)
+                .ok_or_else(|| format!("invalid suffix `{}` for {} literal", name, kind)),
+            None => Ok(None),
+        })
+        .commit()
+}
+
 pub fn float_literal<'a>() -> Parser<'a, Literal> {
-    float().map(|x| Literal::Float(x))
+    strip(float_text().and(typed_suffix("float", FloatSuffix::from_name)))
+        .map(|(text, suffix)| Literal::Float(text, suffix))
+        .label("float")
+}
+
+fn number_suffix(name: &str) -> Option<Result<IntegerSuffix, FloatSuffix>> {
+    IntegerSuffix::from_name(name)
+        .map(Ok)
+        .or_else(|| FloatSuffix::from_name(name).map(Err))
 }
 
 pub fn integer_literal<'a>() -> Parser<'a, Literal> {
-    integer().map(|x| Literal::Integer(x))
+    strip(
+        integer_text()
+            .and(typed_suffix("integer", number_suffix))
+            .validate(|(text, suffix)| match suffix {
+                Some(Err(suffix)) if text.starts_with("0b") || text.starts_with("0o") => Err(
+                    format!("invalid suffix `{}` for integer literal", suffix.name()),
+                ),
+                Some(Err(suffix)) => Ok(Literal::Float(text, Some(suffix))),
+                Some(Ok(suffix)) => Ok(Literal::Integer(text, Some(suffix))),
+                None => Ok(Literal::Integer(text, None)),
+            }),
+    )
+    .label("integer")
 }
 
 pub fn bool_literal<'a>() -> Parser<'a, Literal> {
@@ -18,13 +53,25 @@ pub fn bool_literal<'a>() -> Parser<'a, Literal> {
     })
 }
 
+pub fn string_literal<'a>() -> Parser<'a, Literal> {
+    quoted_string().map(Literal::String)
+}
+
+pub fn char_literal<'a>() -> Parser<'a, Literal> {
+    quoted_char().map(Literal::Char)
+}
+
 pub fn reference_literal<'a>() -> Parser<'a, Literal> {
-    identifier().map(|x| Literal::Reference(x))
+    identifier().map(Literal::Reference)
 }
 
-pub fn literal<'a>() -> Parser<'a, Literal> {
-    float_literal()
-        .or(integer_literal())
-        .or(bool_literal())
-        .or(reference_literal())
+pub fn literal<'a>() -> Parser<'a, Spanned<Literal>> {
+    spanned(
+        float_literal()
+            .or(integer_literal())
+            .or(bool_literal())
+            .or(string_literal())
+            .or(char_literal())
+            .or(reference_literal()),
+    )
 }
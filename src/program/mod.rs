@@ -1,16 +1,20 @@
+use evaluate::Literals;
 use prelude::*;
 
 pub mod ast;
+pub mod diagnostics;
+pub mod evaluate;
 pub mod expression;
 pub mod literal;
+pub mod pattern;
 pub mod statement;
 pub mod tokens;
 pub mod types;
 
 pub mod prelude {
     pub use super::{
-        ast::*, expression::expression, literal::literal, statement::statement, tokens::*,
-        types::types,
+        ast::*, diagnostics::Diagnostics, expression::expression, literal::literal,
+        pattern::pattern, statement::statement, tokens::*, types::types,
     };
     pub use crate::parser::*;
 }
@@ -18,6 +22,20 @@ pub mod prelude {
 pub fn program<'a>() -> Parser<'a, Program> {
     statement()
         .left(semicolon())
-        .many()
-        .map(|statements| Program(statements))
+        .many_till(strip(eof()))
+        .map(Program)
+}
+
+pub fn parse_program(source: &str) -> Result<(Program, Literals), Diagnostics<'_>> {
+    let (program, _) = program()
+        .parse(source)
+        .map_err(|error| Diagnostics::new(source, vec![error]))?;
+
+    match evaluate::evaluate_literals(&program) {
+        Ok(literals) => Ok((program, literals)),
+        Err(errors) => {
+            let errors = errors.iter().map(|error| error.locate(source)).collect();
+            Err(Diagnostics::new(source, errors))
+        }
+    }
 }
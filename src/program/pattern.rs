@@ -0,0 +1,87 @@
+use super::{
+    literal::{bool_literal, char_literal, float_literal, integer_literal, string_literal},
+    prelude::*,
+};
+
+pub fn wildcard<'a>() -> Parser<'a, Pattern> {
+    keyword("_").right(Parser::pure(Pattern::Wildcard))
+}
+
+pub fn binding<'a>() -> Parser<'a, Pattern> {
+    identifier().map(Pattern::Binding)
+}
+
+pub fn literal_pattern<'a>() -> Parser<'a, Pattern> {
+    spanned(
+        float_literal()
+            .or(integer_literal())
+            .or(bool_literal())
+            .or(string_literal())
+            .or(char_literal()),
+    )
+    .map(Pattern::Literal)
+}
+
+pub fn negative_pattern<'a>() -> Parser<'a, Pattern> {
+    symbol("-")
+        .right(spanned(float_literal().or(integer_literal())).commit())
+        .map(Pattern::Negative)
+}
+
+pub fn field_pattern<'a>() -> Parser<'a, FieldPattern> {
+    identifier()
+        .and(otherwise(colon().right(pattern().commit()).map(Some), None))
+        .map(|(name, pattern)| FieldPattern { name, pattern })
+}
+
+pub fn struct_pattern<'a>() -> Parser<'a, Pattern> {
+    identifier()
+        .and(fields(spanned(field_pattern())))
+        .map(|(name, fields)| Pattern::Struct { name, fields })
+}
+
+pub fn variant_pattern<'a>() -> Parser<'a, Pattern> {
+    let values = elements(pattern())
+        .map(VariantPatterns::Tuple)
+        .or(fields(spanned(field_pattern())).map(VariantPatterns::Struct));
+
+    identifier()
+        .left(path_separator())
+        .and(identifier().commit())
+        .and(otherwise(values, VariantPatterns::Unit))
+        .map(|((name, variant), values)| Pattern::Variant {
+            name,
+            variant,
+            values,
+        })
+}
+
+pub fn pattern<'a>() -> Parser<'a, Spanned<Pattern>> {
+    Parser::lazy(|| {
+        spanned(
+            variant_pattern()
+                .or(struct_pattern())
+                .or(literal_pattern())
+                .or(negative_pattern())
+                .or(wildcard())
+                .or(binding()),
+        )
+        .or(parenthesized(pattern(), Pattern::Tuple))
+        .label("pattern")
+    })
+}
+
+pub fn is_irrefutable(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::Wildcard | Pattern::Binding(_) => true,
+        Pattern::Literal(_) | Pattern::Negative(_) | Pattern::Variant { .. } => false,
+        Pattern::Tuple(patterns) => patterns.iter().all(|p| is_irrefutable(&p.node)),
+        Pattern::Struct { fields, .. } => fields.iter().all(|field| {
+            field
+                .node
+                .pattern
+                .as_ref()
+                .is_none_or(|p| is_irrefutable(&p.node))
+        }),
+    }
+}
@@ -1,40 +1,198 @@
-use super::prelude::*;
+use super::{
+    expression::{assign_operator, block_expr, condition},
+    pattern::is_irrefutable,
+    prelude::*,
+    types::generics,
+};
+
+pub fn annotation<'a>() -> Parser<'a, Option<Spanned<Type>>> {
+    otherwise(
+        colon()
+            .right(types().commit())
+            .map(Some)
+            .label("type annotation"),
+        None,
+    )
+}
+
+pub fn let_pattern<'a>() -> Parser<'a, Spanned<Pattern>> {
+    pattern().try_map(|pattern| {
+        if is_irrefutable(&pattern.node) {
+            Ok(pattern)
+        } else {
+            Err("refutable pattern in `let` binding".to_string())
+        }
+    })
+}
 
 pub fn instantiate<'a>() -> Parser<'a, Statement> {
     let_key()
-        .right(identifier())
-        .and(otherwise(colon().right(types()).map(|x| Some(x)), None))
+        .right(let_pattern().commit())
+        .and(annotation())
         .left(equals())
-        .and(expression())
+        .and(expression().commit())
         .map(|((a, b), c)| Statement::Instantiate(b, a, c))
 }
 
-pub fn assign<'a>() -> Parser<'a, Statement> {
-    identifier()
-        .left(equals())
-        .and(expression())
-        .map(|(name, expr)| Statement::Assign(name, expr))
+fn is_place(expression: &Expression) -> bool {
+    match expression {
+        Expression::Literal(literal) => matches!(literal.node, Literal::Reference(_)),
+        Expression::Index { target, .. }
+        | Expression::Field { target, .. }
+        | Expression::Element { target, .. } => is_place(&target.node),
+        _ => false,
+    }
+}
+
+pub fn assignment<'a>() -> Parser<'a, (Option<BinaryOperator>, Spanned<Expression>)> {
+    assign_operator().and(expression().commit())
 }
 
 pub fn declare<'a>() -> Parser<'a, Statement> {
     let_key()
-        .right(identifier())
-        .and(otherwise(colon().right(types()).map(|x| Some(x)), None))
+        .right(identifier().commit())
+        .and(annotation())
         .map(|(a, b)| Statement::Declare(b, a))
 }
 
 pub fn variable<'a>() -> Parser<'a, Statement> {
-    instantiate().or(assign()).or(declare())
+    instantiate().or(declare())
+}
+
+pub fn parameter<'a>() -> Parser<'a, Parameter> {
+    identifier()
+        .left(colon())
+        .and(types().commit())
+        .map(|(name, annotation)| Parameter { name, annotation })
+}
+
+pub fn parameters<'a>() -> Parser<'a, Vec<Spanned<Parameter>>> {
+    elements(spanned(parameter()))
+}
+
+pub fn output<'a>() -> Parser<'a, Option<Spanned<Type>>> {
+    otherwise(
+        arrow()
+            .right(types().commit())
+            .map(Some)
+            .label("return type"),
+        None,
+    )
+}
+
+pub fn function<'a>() -> Parser<'a, Statement> {
+    fn_key()
+        .right(identifier().commit())
+        .and(generics())
+        .and(parameters().commit())
+        .and(output().and(spanned(block_expr())).commit())
+        .map(
+            |(((name, generics), parameters), (output, body))| Statement::Function {
+                name,
+                generics,
+                parameters,
+                output,
+                body,
+            },
+        )
+}
+
+pub fn struct_field<'a>() -> Parser<'a, StructField> {
+    identifier()
+        .left(colon().commit())
+        .and(types().commit())
+        .map(|(name, annotation)| StructField { name, annotation })
+}
+
+pub fn struct_stat<'a>() -> Parser<'a, Statement> {
+    struct_key()
+        .right(identifier().commit())
+        .and(generics())
+        .and(fields(spanned(struct_field())).commit())
+        .map(|((name, generics), fields)| Statement::Struct {
+            name,
+            generics,
+            fields,
+        })
+}
+
+pub fn enum_variant<'a>() -> Parser<'a, EnumVariant> {
+    let tuple_fields = elements(types()).map(VariantFields::Tuple);
+    let struct_fields = fields(spanned(struct_field())).map(VariantFields::Struct);
+
+    identifier()
+        .and(otherwise(
+            tuple_fields.or(struct_fields),
+            VariantFields::Unit,
+        ))
+        .map(|(name, fields)| EnumVariant { name, fields })
+}
+
+pub fn enum_stat<'a>() -> Parser<'a, Statement> {
+    enum_key()
+        .right(identifier().commit())
+        .and(fields(spanned(enum_variant())).commit())
+        .map(|(name, variants)| Statement::Enum { name, variants })
+}
+
+pub fn while_stat<'a>() -> Parser<'a, Statement> {
+    while_key()
+        .right(condition().commit())
+        .and(spanned(block_expr()).commit())
+        .map(|(condition, body)| Statement::While { condition, body })
+}
+
+pub fn for_stat<'a>() -> Parser<'a, Statement> {
+    for_key()
+        .right(identifier().commit())
+        .left(in_key().commit())
+        .and(condition().commit())
+        .and(spanned(block_expr()).commit())
+        .map(|((binding, iterable), body)| Statement::For {
+            binding,
+            iterable,
+            body,
+        })
+}
+
+pub fn break_stat<'a>() -> Parser<'a, Statement> {
+    break_key()
+        .right(otherwise(expression().map(Some), None))
+        .map(Statement::Break)
+}
+
+pub fn continue_stat<'a>() -> Parser<'a, Statement> {
+    continue_key().right(Parser::pure(Statement::Continue))
 }
 
 pub fn expression_stat<'a>() -> Parser<'a, Statement> {
-    expression().map(|x| Statement::Expression(x))
+    expression()
+        .and(otherwise(assignment().map(Some), None))
+        .validate(|(target, assignment)| match assignment {
+            None => Ok(Statement::Expression(target)),
+            Some(_) if !is_place(&target.node) => Err("invalid assignment target".to_string()),
+            Some((operator, value)) => Ok(Statement::Assign(target, operator, value)),
+        })
 }
 
 pub fn no_op<'a>() -> Parser<'a, Statement> {
-    Parser::pure(Statement::NoOp)
+    lookahead(symbol(";").or(symbol("}"))).right(Parser::pure(Statement::NoOp))
 }
 
-pub fn statement<'a>() -> Parser<'a, Statement> {
-    Parser::lazy(|| strip(variable().or(expression_stat()).or(no_op())))
+pub fn statement<'a>() -> Parser<'a, Spanned<Statement>> {
+    Parser::lazy(|| {
+        spanned(strip(
+            variable()
+                .or(function())
+                .or(struct_stat())
+                .or(enum_stat())
+                .or(while_stat())
+                .or(for_stat())
+                .or(break_stat())
+                .or(continue_stat())
+                .or(expression_stat())
+                .or(no_op()),
+        ))
+        .label("statement")
+    })
 }
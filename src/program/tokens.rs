@@ -3,28 +3,100 @@ use super::prelude::*;
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub enum Token {
     Let,
+    Fn,
+    If,
+    Else,
+    Loop,
+    While,
+    For,
+    In,
+    Break,
+    Continue,
+    Struct,
+    Enum,
+    Match,
     True,
     False,
     Equals,
     Colon,
     Semicolon,
     Comma,
+    Arrow,
+    PathSeparator,
+    FatArrow,
+}
+
+pub const RESERVED: &[&str] = &[
+    "let", "fn", "if", "else", "loop", "while", "for", "in", "break", "continue", "struct", "enum",
+    "match", "true", "false",
+];
+
+pub fn identifier<'a>() -> Parser<'a, String> {
+    identifier_excluding(RESERVED)
 }
 
 pub fn let_key<'a>() -> Parser<'a, Token> {
-    symbol("let").right(Parser::pure(Token::Let))
+    keyword("let").right(Parser::pure(Token::Let))
+}
+
+pub fn fn_key<'a>() -> Parser<'a, Token> {
+    keyword("fn").right(Parser::pure(Token::Fn))
+}
+
+pub fn if_key<'a>() -> Parser<'a, Token> {
+    keyword("if").right(Parser::pure(Token::If))
+}
+
+pub fn else_key<'a>() -> Parser<'a, Token> {
+    keyword("else").right(Parser::pure(Token::Else))
+}
+
+pub fn loop_key<'a>() -> Parser<'a, Token> {
+    keyword("loop").right(Parser::pure(Token::Loop))
+}
+
+pub fn while_key<'a>() -> Parser<'a, Token> {
+    keyword("while").right(Parser::pure(Token::While))
+}
+
+pub fn for_key<'a>() -> Parser<'a, Token> {
+    keyword("for").right(Parser::pure(Token::For))
+}
+
+pub fn in_key<'a>() -> Parser<'a, Token> {
+    keyword("in").right(Parser::pure(Token::In))
+}
+
+pub fn break_key<'a>() -> Parser<'a, Token> {
+    keyword("break").right(Parser::pure(Token::Break))
+}
+
+pub fn continue_key<'a>() -> Parser<'a, Token> {
+    keyword("continue").right(Parser::pure(Token::Continue))
+}
+
+pub fn struct_key<'a>() -> Parser<'a, Token> {
+    keyword("struct").right(Parser::pure(Token::Struct))
+}
+
+pub fn enum_key<'a>() -> Parser<'a, Token> {
+    keyword("enum").right(Parser::pure(Token::Enum))
+}
+
+pub fn match_key<'a>() -> Parser<'a, Token> {
+    keyword("match").right(Parser::pure(Token::Match))
 }
 
 pub fn true_key<'a>() -> Parser<'a, Token> {
-    symbol("true").right(Parser::pure(Token::True))
+    keyword("true").right(Parser::pure(Token::True))
 }
 
 pub fn false_key<'a>() -> Parser<'a, Token> {
-    symbol("false").right(Parser::pure(Token::False))
+    keyword("false").right(Parser::pure(Token::False))
 }
 
 pub fn equals<'a>() -> Parser<'a, Token> {
-    symbol("=").right(Parser::pure(Token::Equals))
+    strip(string("=").left(not(char('=')))).right(Parser::pure(Token::Equals))
 }
 
 pub fn colon<'a>() -> Parser<'a, Token> {
@@ -38,3 +110,70 @@ pub fn semicolon<'a>() -> Parser<'a, Token> {
 pub fn comma<'a>() -> Parser<'a, Token> {
     symbol(",").right(Parser::pure(Token::Comma))
 }
+
+pub fn arrow<'a>() -> Parser<'a, Token> {
+    symbol("->").right(Parser::pure(Token::Arrow))
+}
+
+pub fn path_separator<'a>() -> Parser<'a, Token> {
+    symbol("::").right(Parser::pure(Token::PathSeparator))
+}
+
+pub fn fat_arrow<'a>() -> Parser<'a, Token> {
+    symbol("=>").right(Parser::pure(Token::FatArrow))
+}
+
+pub fn comma_list<'a, T: 'a>(element: Parser<'a, T>, close: &'a str) -> Parser<'a, (Vec<T>, bool)> {
+    let close = symbol(close);
+    let comma = comma();
+
+    Parser::new(move |mut input: Input<'a>| {
+        let mut results = Vec::new();
+        loop {
+            let stop = match close.run(input) {
+                Ok(_) => {
+                    let trailing = !results.is_empty();
+                    return Ok(((results, trailing), input));
+                }
+                Err(error) => error,
+            };
+
+            let (result, remaining) = element.run(input).map_err(|error| {
+                if error.committed {
+                    error
+                } else {
+                    error.merge(stop)
+                }
+            })?;
+            results.push(result);
+
+            input = match close.run(remaining) {
+                Ok(_) => return Ok(((results, false), remaining)),
+                Err(stop) => comma.run(remaining).map_err(|error| stop.merge(error))?.1,
+            };
+        }
+    })
+}
+
+pub fn elements<'a, T: 'a>(element: Parser<'a, T>) -> Parser<'a, Vec<T>> {
+    tuple(comma_list(element, ")").commit()).map(|(elements, _)| elements)
+}
+
+pub fn parenthesized<'a, T, F>(element: Parser<'a, Spanned<T>>, wrap: F) -> Parser<'a, Spanned<T>>
+where
+    T: 'a,
+    F: 'a + Fn(Vec<Spanned<T>>) -> T,
+{
+    spanned(tuple(comma_list(element, ")"))).map(move |list| {
+        let (mut elements, trailing) = list.node;
+        if elements.len() == 1 && !trailing {
+            Spanned::new(elements.remove(0).node, list.span)
+        } else {
+            Spanned::new(wrap(elements), list.span)
+        }
+    })
+}
+
+pub fn fields<'a, T: 'a>(field: Parser<'a, T>) -> Parser<'a, Vec<T>> {
+    block(comma_list(field, "}")).map(|(fields, _)| fields)
+}
@@ -1,13 +1,43 @@
 use super::prelude::*;
 
 pub fn atomic<'a>() -> Parser<'a, Type> {
-    identifier().map(|c| Type::Atomic(c))
+    identifier().map(Type::Atomic)
+}
+
+pub fn generic<'a>() -> Parser<'a, Type> {
+    identifier()
+        .and(symbol("<").right(separated(types(), comma()).left(symbol(">")).commit()))
+        .map(|(name, arguments)| Type::Generic(name, arguments))
 }
 
 pub fn array<'a>() -> Parser<'a, Type> {
     set(types()).map(|c| Type::Array(Box::new(c)))
 }
 
-pub fn types<'a>() -> Parser<'a, Type> {
-    Parser::lazy(|| array().or(atomic()))
+pub fn types<'a>() -> Parser<'a, Spanned<Type>> {
+    Parser::lazy(|| {
+        spanned(array().or(generic()).or(atomic()))
+            .or(parenthesized(types(), Type::Tuple))
+            .label("type")
+    })
+}
+
+pub fn generic_parameter<'a>() -> Parser<'a, GenericParameter> {
+    identifier()
+        .and(otherwise(
+            colon().right(separated(types(), symbol("+")).commit()),
+            vec![],
+        ))
+        .map(|(name, bounds)| GenericParameter { name, bounds })
+}
+
+pub fn generics<'a>() -> Parser<'a, Vec<Spanned<GenericParameter>>> {
+    otherwise(
+        symbol("<").right(
+            separated(spanned(generic_parameter()), comma())
+                .left(symbol(">"))
+                .commit(),
+        ),
+        vec![],
+    )
 }
@@ -22,7 +22,10 @@ mod library {
         let a_and_b_parser = char('a').and(char('b'));
 
         assert_eq!(a_and_b_parser.parse("abc"), Ok((('a', 'b'), "c")));
-        assert_eq!(a_and_b_parser.parse("aac"), Err("Character mismatch"));
+        assert_eq!(
+            a_and_b_parser.parse("aac").unwrap_err().to_string(),
+            "1:2: expected `b`, found `a`"
+        );
 
         let a_left_b_parser = char('a').left(char('b'));
 
@@ -35,26 +38,147 @@ mod library {
 
     #[test]
     fn alternative_parsers() {
-        assert_eq!(Parser::<()>::empty("failed").parse("abc"), Err("failed"));
+        assert_eq!(
+            Parser::<()>::empty("failed")
+                .parse("abc")
+                .unwrap_err()
+                .to_string(),
+            "1:1: failed"
+        );
 
         let a_or_b_parser = char('a').or(char('b'));
 
         assert_eq!(a_or_b_parser.parse("abc"), Ok(('a', "bc")));
         assert_eq!(a_or_b_parser.parse("bbc"), Ok(('b', "bc")));
-        assert_eq!(a_or_b_parser.parse("cbc"), Err("Character mismatch"));
+        assert_eq!(
+            a_or_b_parser.parse("cbc").unwrap_err().to_string(),
+            "1:1: expected `a` or `b`, found `c`"
+        );
+    }
+
+    #[test]
+    fn alternative_errors() {
+        let shallow = char('a').and(char('b')).map(|_| ());
+        let deep = char('a').and(char('c')).and(char('d')).map(|_| ());
+
+        assert_eq!(
+            shallow
+                .clone()
+                .or(deep.clone())
+                .parse("acx")
+                .unwrap_err()
+                .to_string(),
+            "1:3: expected `d`, found `x`"
+        );
+        assert_eq!(
+            deep.or(shallow).parse("acx").unwrap_err().to_string(),
+            "1:3: expected `d`, found `x`"
+        );
+
+        let repeated = char('a').or(char('b')).or(char('a'));
+
+        assert_eq!(
+            repeated.parse("c").unwrap_err().expected,
+            vec!["`a`".to_string(), "`b`".to_string()]
+        );
+    }
+
+    #[test]
+    fn labelled_parsers() {
+        let pair = char('a').and(char('b')).label("pair");
+
+        assert_eq!(
+            pair.parse("xb").unwrap_err().to_string(),
+            "1:1: expected pair, found `x`"
+        );
+        assert_eq!(
+            pair.parse("ax").unwrap_err().to_string(),
+            "1:2: expected `b`, found `x`"
+        );
+        assert_eq!(
+            strip(pair).parse("  x").unwrap_err().to_string(),
+            "1:3: expected pair, found `x`"
+        );
+        assert_eq!(
+            strip(char('a'))
+                .label("a")
+                .parse("  x")
+                .unwrap_err()
+                .to_string(),
+            "1:3: expected a, found `x`"
+        );
+    }
+
+    #[test]
+    fn committed_parsers() {
+        let committed = char('a').right(char('b').commit());
+        let fallback = char('a').right(char('c'));
+
+        assert_eq!(
+            committed.clone().or(fallback.clone()).parse("ac"),
+            Err(ParseError {
+                position: Position {
+                    offset: 1,
+                    line: 1,
+                    column: 2
+                },
+                expected: vec!["`b`".to_string()],
+                found: Some("`c`".to_string()),
+                message: None,
+                committed: true,
+            })
+        );
+        assert_eq!(fallback.or(committed.clone()).parse("ac"), Ok(('c', "")));
+        assert_eq!(
+            committed.many().parse("abac").unwrap_err().to_string(),
+            "1:4: expected `b`, found `c`"
+        );
     }
 
     #[test]
     fn parse_any() {
         assert_eq!(any().parse("abc"), Ok(('a', "bc")));
-        assert_eq!(any().parse(""), Err("Nothing to parse"));
+        assert_eq!(
+            any().parse("").unwrap_err().to_string(),
+            "1:1: expected any character, found end of input"
+        );
+    }
+
+    #[test]
+    fn parse_eof() {
+        assert_eq!(eof().parse(""), Ok(((), "")));
+        assert_eq!(
+            eof().parse("a").unwrap_err().to_string(),
+            "1:1: expected end of input, found `a`"
+        );
     }
 
     #[test]
     fn parse_char() {
         assert_eq!(char('a').parse("abc"), Ok(('a', "bc")));
-        assert_eq!(char('a').parse("xyz"), Err("Character mismatch"));
-        assert_eq!(char('a').parse(""), Err("Character mismatch"));
+        assert_eq!(
+            char('a').parse("xyz").unwrap_err().to_string(),
+            "1:1: expected `a`, found `x`"
+        );
+        assert_eq!(
+            char('a').parse("").unwrap_err().to_string(),
+            "1:1: expected `a`, found end of input"
+        );
+    }
+
+    #[test]
+    fn parse_error_position() {
+        let error = string("ab\ncd")
+            .right(char('e'))
+            .parse("ab\ncdx")
+            .unwrap_err();
+
+        assert_eq!(error.position.offset, 5);
+        assert_eq!(error.position.line, 2);
+        assert_eq!(error.position.column, 3);
+        assert_eq!(error.expected, vec!["`e`".to_string()]);
+        assert_eq!(error.found, Some("`x`".to_string()));
+        assert_eq!(error.to_string(), "2:3: expected `e`, found `x`");
     }
 
     #[test]
@@ -65,8 +189,48 @@ mod library {
         );
 
         assert_eq!(
-            whitespace().many().parse("    a"),
-            Ok((vec![' ', ' ', ' ', ' '], "a"))
+            digit().many().parse("1234a"),
+            Ok((vec!['1', '2', '3', '4'], "a"))
+        );
+    }
+
+    #[test]
+    fn parse_many_without_progress() {
+        assert_eq!(Parser::pure('a').many().parse("abc"), Ok((vec![], "abc")));
+        assert_eq!(
+            otherwise(char('a'), 'z').many().parse("aab"),
+            Ok((vec!['a', 'a'], "b"))
+        );
+        assert_eq!(
+            Parser::pure('a').some().parse("abc"),
+            Ok((vec!['a'], "abc"))
+        );
+        assert_eq!(
+            separated(Parser::pure('a'), Parser::pure(())).parse("abc"),
+            Ok((vec!['a'], "abc"))
+        );
+        assert_eq!(
+            Parser::pure('a')
+                .many_till(eof())
+                .parse("abc")
+                .unwrap_err()
+                .to_string(),
+            "1:1: expected end of input, found `a`"
+        );
+    }
+
+    #[test]
+    fn parse_many_till() {
+        let pairs = char('a').right(char('b')).many_till(eof());
+
+        assert_eq!(pairs.parse("abab"), Ok((vec!['b', 'b'], "")));
+        assert_eq!(
+            pairs.parse("abac").unwrap_err().to_string(),
+            "1:4: expected `b`, found `c`"
+        );
+        assert_eq!(
+            pairs.parse("abx").unwrap_err().to_string(),
+            "1:3: expected end of input or `a`, found `x`"
         );
     }
 
@@ -76,11 +240,17 @@ mod library {
 
         assert_eq!(a_or_b_parser.parse("abc"), Ok(('a', "bc")));
         assert_eq!(a_or_b_parser.parse("bbc"), Ok(('b', "bc")));
-        assert_eq!(a_or_b_parser.parse("cbc"), Err("Character mismatch"));
+        assert_eq!(
+            a_or_b_parser.parse("cbc").unwrap_err().to_string(),
+            "1:1: expected `a` or `b`, found `c`"
+        );
 
         let empty_list = list(&[]);
 
-        assert_eq!(empty_list.parse("abc"), Err("List parser has no members"));
+        assert_eq!(
+            empty_list.parse("abc").unwrap_err().to_string(),
+            "1:1: List parser has no members"
+        );
     }
 
     #[test]
@@ -88,7 +258,10 @@ mod library {
         let between_parser = between(digit(), letter(), digit());
 
         assert_eq!(between_parser.parse("1a1"), Ok(('a', "")));
-        assert_eq!(between_parser.parse("aa1"), Err("Character mismatch"));
+        assert_eq!(
+            between_parser.parse("aa1").unwrap_err().to_string(),
+            "1:1: expected digit, found `a`"
+        );
     }
 
     #[test]
@@ -100,16 +273,16 @@ mod library {
     }
 
     #[test]
-    fn parse_delimited() {
-        let delimited_parser = delimited(alphanumeric(), char(','));
+    fn parse_separated() {
+        let separated_parser = separated(alphanumeric(), char(','));
 
         assert_eq!(
-            delimited_parser.parse("1,2,3,4"),
+            separated_parser.parse("1,2,3,4"),
             Ok((vec!['1', '2', '3', '4'], ""))
         );
 
         assert_eq!(
-            delimited_parser.parse("1,2,3,,5"),
+            separated_parser.parse("1,2,3,,5"),
             Ok((vec!['1', '2', '3'], ",,5"))
         );
     }
@@ -122,196 +295,2413 @@ mod library {
     }
 
     #[test]
-    fn parse_string() {
-        let string_parser = string("hello");
+    fn parse_spanned() {
+        assert_eq!(
+            spanned(symbol("ab")).parse("  ab  c"),
+            Ok((Spanned::new("ab".to_string(), Span::new(2, 4)), "c"))
+        );
+        assert_eq!(
+            spanned(symbol("a").and(symbol("b"))).parse("a \n b"),
+            Ok((
+                Spanned::new(("a".to_string(), "b".to_string()), Span::new(0, 5)),
+                ""
+            ))
+        );
+        assert_eq!(
+            spanned(Parser::pure(1)).parse("  x"),
+            Ok((Spanned::new(1, Span::new(0, 0)), "  x"))
+        );
+    }
 
-        assert_eq!(string_parser.parse("hello"), Ok(("hello".to_string(), "")));
-        assert_eq!(string_parser.parse("yello"), Err("Character mismatch"));
+    #[test]
+    fn parse_not() {
+        assert_eq!(char('a').left(not(char('b'))).parse("ac"), Ok(('a', "c")));
+        assert_eq!(
+            char('a')
+                .left(not(char('b')))
+                .parse("ab")
+                .unwrap_err()
+                .to_string(),
+            "1:2: unexpected `b`"
+        );
     }
 
     #[test]
-    fn parse_identifier() {
-        assert_eq!(identifier().parse("1id"), Err("Character mismatch"));
+    fn parse_climb() {
+        let number = strip(integer_text())
+            .label("integer")
+            .map(|x| x.parse::<i64>().unwrap());
+        let operator = |a: &'static str| symbol(a).right(Parser::pure(a));
+        let calculator = climb(
+            number,
+            vec![
+                infix(operator("+"), 1, Associativity::Left),
+                infix(operator("-"), 1, Associativity::Left),
+                infix(operator("*"), 2, Associativity::Left),
+                infix(operator("^"), 3, Associativity::Right),
+                infix(operator("**"), 3, Associativity::Right),
+                infix(operator("=="), 0, Associativity::None),
+            ],
+            |operator, a, b| match operator {
+                "+" => a + b,
+                "-" => a - b,
+                "*" => a * b,
+                "==" => (a == b) as i64,
+                _ => a.pow(b as u32),
+            },
+        );
+
+        assert_eq!(calculator.parse("1 + 2 * 3"), Ok((7, "")));
+        assert_eq!(calculator.parse("10 - 4 - 3"), Ok((3, "")));
+        assert_eq!(calculator.parse("2 ^ 3 ^ 2"), Ok((512, "")));
+        assert_eq!(calculator.parse("2 * 2 ** 3"), Ok((16, "")));
+        assert_eq!(calculator.parse("1 + 1 == 2"), Ok((1, "")));
         assert_eq!(
-            identifier().parse("helloIAmIdent"),
-            Ok(("helloIAmIdent".to_string(), ""))
+            calculator.parse("1 == 1 == 1").unwrap_err().to_string(),
+            "1:8: non-associative operators cannot be chained"
+        );
+        assert_eq!(calculator.parse("2 * 3 + 4 x"), Ok((10, "x")));
+        assert_eq!(
+            calculator.parse("2 * ").unwrap_err().to_string(),
+            "1:5: expected integer, found end of input"
         );
     }
 
     #[test]
-    fn parse_symbol() {
-        assert_eq!(symbol("if").parse("if"), Ok(("if".to_string(), "")));
-        assert_eq!(symbol("if").parse("else"), Err("Character mismatch"));
+    fn parse_none_of() {
+        assert_eq!(none_of(&['a']).parse("ba"), Ok(('b', "a")));
+        assert_eq!(
+            none_of(&['a']).parse("ab").unwrap_err().to_string(),
+            "1:1: unexpected `a`"
+        );
+        assert_eq!(
+            none_of(&['a']).parse("").unwrap_err().to_string(),
+            "1:1: unexpected end of input"
+        );
     }
 
     #[test]
-    fn parse_accumulators() {
+    fn parse_try_map() {
+        let even = digit().try_map(|c| match c.to_digit(10).unwrap() {
+            x if x % 2 == 0 => Ok(x),
+            x => Err(format!("{} is odd", x)),
+        });
+
+        assert_eq!(even.parse("4"), Ok((4, "")));
+        assert_eq!(even.parse("3").unwrap_err().to_string(), "1:1: 3 is odd");
+    }
+
+    #[test]
+    fn parse_validate() {
+        let even = otherwise(
+            digit().validate(|c| match c.to_digit(10).unwrap() {
+                x if x % 2 == 0 => Ok(x),
+                x => Err(format!("{} is odd", x)),
+            }),
+            0,
+        );
+
+        assert_eq!(even.parse("4"), Ok((4, "")));
+        assert_eq!(even.parse("x"), Ok((0, "x")));
+        assert_eq!(even.parse("3").unwrap_err().to_string(), "1:1: 3 is odd");
+    }
+
+    #[test]
+    fn parse_quoted_string() {
         assert_eq!(
-            tuple(identifier()).parse("(abc)"),
-            Ok(("abc".to_string(), ""))
+            quoted_string().parse(r#" "a\"b\n\t\\\x41\u{1F600}" "#),
+            Ok(("a\"b\n\t\\A\u{1F600}".to_string(), ""))
         );
+        assert_eq!(quoted_string().parse(r#""""#), Ok(("".to_string(), "")));
         assert_eq!(
-            set(identifier()).parse("[abc]"),
-            Ok(("abc".to_string(), ""))
+            quoted_string().parse(r#""abc"#).unwrap_err().to_string(),
+            "1:5: expected `\"`, found end of input"
         );
         assert_eq!(
-            block(identifier()).parse("{abc}"),
-            Ok(("abc".to_string(), ""))
+            quoted_string().parse(r#""a\q""#).unwrap_err().to_string(),
+            "1:4: expected escape sequence, found `q`"
+        );
+        assert_eq!(
+            quoted_string().parse(r#""\x9z""#).unwrap_err().to_string(),
+            "1:5: expected hex digit, found `z`"
+        );
+        assert_eq!(
+            quoted_string().parse(r#""\xff""#).unwrap_err().to_string(),
+            "1:3: hex escape `\\xff` is out of range"
+        );
+        assert_eq!(
+            quoted_string()
+                .parse(r#""\u{d800}""#)
+                .unwrap_err()
+                .to_string(),
+            "1:3: invalid unicode escape `\\u{d800}`"
         );
     }
 
     #[test]
-    fn parse_integer() {
-        assert_eq!(integer().parse("483943"), Ok(("483943".to_string(), "")));
+    fn parse_quoted_char() {
+        assert_eq!(quoted_char().parse("'a'"), Ok(('a', "")));
+        assert_eq!(quoted_char().parse(r"'\''"), Ok(('\'', "")));
+        assert_eq!(quoted_char().parse(r"'\u{e9}'"), Ok(('é', "")));
         assert_eq!(
-            integer().parse("483943jkds"),
-            Ok(("483943".to_string(), "jkds"))
+            quoted_char().parse("'ab'").unwrap_err().to_string(),
+            "1:3: expected `'`, found `b`"
+        );
+        assert_eq!(
+            quoted_char().parse("x").unwrap_err().to_string(),
+            "1:1: expected character, found `x`"
         );
     }
 
     #[test]
-    fn parse_float() {
-        assert_eq!(float().parse("12.32f"), Ok(("12.32".to_string(), "")));
-        assert_eq!(float().parse("12.32"), Ok(("12.32".to_string(), "")));
-        assert_eq!(float().parse("12."), Ok(("12.".to_string(), "")));
-        assert_eq!(float().parse("12"), Err("Character mismatch"));
+    fn parse_trivia() {
+        assert_eq!(
+            strip(integer_text()).parse(" // one\n 1 /* two */ x"),
+            Ok(("1".to_string(), "x"))
+        );
+        assert_eq!(
+            word().parse("/* a /* nested */ comment */abc// trailing"),
+            Ok(("abc".to_string(), ""))
+        );
+        assert_eq!(
+            spanned(word()).parse("/**/ab /**/"),
+            Ok((Spanned::new("ab".to_string(), Span::new(4, 6)), ""))
+        );
+        assert_eq!(
+            word()
+                .or(integer_text())
+                .parse("  /* open /* */")
+                .unwrap_err()
+                .to_string(),
+            "1:3: unterminated block comment"
+        );
     }
-}
 
-#[cfg(test)]
-mod syntax {
-    use crate::{
-        ast::*,
-        expression::{block_expr, literal_expr},
-        literal::*,
-        program,
-        statement::*,
-        types::*,
-    };
+    #[test]
+    fn parse_string() {
+        let string_parser = string("hello");
+
+        assert_eq!(string_parser.parse("hello"), Ok(("hello".to_string(), "")));
+        assert_eq!(
+            string_parser.parse("yello").unwrap_err().to_string(),
+            "1:1: expected `hello`, found `y`"
+        );
+    }
 
     #[test]
-    fn parse_primitive() {
+    fn parse_identifier() {
         assert_eq!(
-            atomic().parse("i32"),
-            Ok((Type::Atomic("i32".to_string()), ""))
+            word().parse("1id").unwrap_err().to_string(),
+            "1:1: expected identifier, found `1`"
+        );
+        assert_eq!(
+            word().parse("helloIAmIdent"),
+            Ok(("helloIAmIdent".to_string(), ""))
         );
     }
 
     #[test]
-    fn parse_float_literal() {
+    fn parse_keyword() {
+        assert_eq!(keyword("let").parse(" let x"), Ok(("let".to_string(), "x")));
         assert_eq!(
-            float_literal().parse("12378.5"),
-            Ok((Literal::Float("12378.5".to_string()), ""))
-        )
+            keyword("let").parse("let(x)"),
+            Ok(("let".to_string(), "(x)"))
+        );
+        assert_eq!(
+            keyword("let").parse("letter").unwrap_err().to_string(),
+            "1:1: expected `let`, found `l`"
+        );
+        assert_eq!(
+            keyword("true").parse("true_").unwrap_err().to_string(),
+            "1:1: expected `true`, found `t`"
+        );
     }
 
     #[test]
-    fn parse_integer_literal() {
+    fn parse_reserved_identifier() {
+        let identifier = identifier_excluding(&["let"]);
+
+        assert_eq!(identifier.parse("lets"), Ok(("lets".to_string(), "")));
         assert_eq!(
-            integer_literal().parse("12378"),
-            Ok((Literal::Integer("12378".to_string()), ""))
-        )
+            identifier.parse(" let").unwrap_err().to_string(),
+            "1:2: expected identifier, found keyword `let`"
+        );
+        assert_eq!(word().parse("let"), Ok(("let".to_string(), "")));
     }
 
     #[test]
-    fn parse_boolean_literal() {
+    fn parse_symbol() {
+        assert_eq!(symbol("if").parse("if"), Ok(("if".to_string(), "")));
         assert_eq!(
-            bool_literal().parse("true"),
-            Ok((Literal::Boolean(true), ""))
-        )
+            symbol("if").parse("else").unwrap_err().to_string(),
+            "1:1: expected `if`, found `e`"
+        );
     }
 
     #[test]
-    fn parse_reference_literal() {
+    fn parse_accumulators() {
+        assert_eq!(tuple(word()).parse("(abc)"), Ok(("abc".to_string(), "")));
+        assert_eq!(set(word()).parse("[abc]"), Ok(("abc".to_string(), "")));
+        assert_eq!(block(word()).parse("{abc}"), Ok(("abc".to_string(), "")));
+    }
+
+    #[test]
+    fn parse_integer() {
         assert_eq!(
-            reference_literal().parse("jlfd3"),
-            Ok((Literal::Reference("jlfd3".to_string()), ""))
-        )
+            integer_text().parse("483943"),
+            Ok(("483943".to_string(), ""))
+        );
+        assert_eq!(
+            integer_text().parse("483943jkds"),
+            Ok(("483943".to_string(), "jkds"))
+        );
+        assert_eq!(
+            integer_text().parse("1_000_000"),
+            Ok(("1000000".to_string(), ""))
+        );
+        assert_eq!(
+            integer_text().parse("0xFF_ff"),
+            Ok(("0xFFff".to_string(), ""))
+        );
+        assert_eq!(integer_text().parse("0o17"), Ok(("0o17".to_string(), "")));
+        assert_eq!(
+            integer_text().parse("0b1010"),
+            Ok(("0b1010".to_string(), ""))
+        );
+        assert_eq!(
+            integer_text().parse("0b102").map(|(text, _)| text),
+            Ok("0b10".to_string())
+        );
+        assert_eq!(
+            integer_text().parse("0xg").unwrap_err().to_string(),
+            "1:3: expected hex digit, found `g`"
+        );
     }
 
     #[test]
-    fn parse_literal_expr() {
+    fn parse_float() {
+        assert_eq!(float_text().parse("12.32f"), Ok(("12.32".to_string(), "f")));
+        assert_eq!(float_text().parse("12.32"), Ok(("12.32".to_string(), "")));
         assert_eq!(
-            literal_expr().parse("123"),
-            Ok((Expression::Literal(Literal::Integer("123".to_string())), ""))
+            float_text().parse("1_0.5e-3"),
+            Ok(("10.5e-3".to_string(), ""))
+        );
+        assert_eq!(float_text().parse("2E10"), Ok(("2e10".to_string(), "")));
+        assert_eq!(
+            float_text().parse("12.").unwrap_err().to_string(),
+            "1:4: expected digit, found end of input"
+        );
+        assert_eq!(
+            float_text().parse("12").unwrap_err().to_string(),
+            "1:3: expected `.`, `e` or `E`, found end of input"
+        );
+    }
+}
+
+#[cfg(test)]
+mod syntax {
+    use crate::{
+        ast::*,
+        evaluate::{Literals, Number},
+        expression::{block_expr, expression, literal_expr},
+        literal::*,
+        parser::{Span, Spanned},
+        pattern::pattern,
+        program,
+        statement::*,
+        tokens::*,
+        types::*,
+    };
+
+    fn at<T>(node: T, start: usize, end: usize) -> Spanned<T> {
+        Spanned::new(node, Span::new(start, end))
+    }
+
+    fn integer_at(text: &str, start: usize) -> Spanned<Expression> {
+        let end = start + text.len();
+        at(
+            Expression::Literal(at(Literal::Integer(text.to_string(), None), start, end)),
+            start,
+            end,
         )
     }
 
     #[test]
-    fn parse_block_expr() {
+    fn parse_primitive() {
         assert_eq!(
-            block_expr().parse("{let a = 123;}"),
-            Ok((
-                Expression::Block(vec![Statement::Instantiate(
-                    None,
-                    "a".to_string(),
-                    Expression::Literal(Literal::Integer("123".to_string()))
-                )]),
-                ""
-            ))
-        )
+            atomic().parse("i32"),
+            Ok((Type::Atomic("i32".to_string()), ""))
+        );
     }
 
     #[test]
-    fn parse_declare() {
+    fn parse_tuple_types() {
         assert_eq!(
-            declare().parse("let a: int"),
+            types().parse("(i32, [bool])"),
             Ok((
-                Statement::Declare(Some(Type::Atomic("int".to_string())), "a".to_string()),
+                at(
+                    Type::Tuple(vec![
+                        at(Type::Atomic("i32".to_string()), 1, 4),
+                        at(
+                            Type::Array(Box::new(at(Type::Atomic("bool".to_string()), 7, 11))),
+                            6,
+                            12
+                        ),
+                    ]),
+                    0,
+                    13
+                ),
                 ""
             ))
         );
+        assert_eq!(types().parse("()"), Ok((at(Type::Tuple(vec![]), 0, 2), "")));
         assert_eq!(
-            declare().parse("let a"),
-            Ok((Statement::Declare(None, "a".to_string()), ""))
+            types().parse("(i32)"),
+            Ok((at(Type::Atomic("i32".to_string()), 0, 5), ""))
         );
     }
 
     #[test]
-    fn parse_assignment() {
+    fn parse_generic_types() {
         assert_eq!(
-            assign().parse("a = 50"),
+            types().parse("Map<String, Vec<[i32]>>"),
             Ok((
-                Statement::Assign(
-                    "a".to_string(),
-                    Expression::Literal(Literal::Integer("50".to_string()))
+                at(
+                    Type::Generic(
+                        "Map".to_string(),
+                        vec![
+                            at(Type::Atomic("String".to_string()), 4, 10),
+                            at(
+                                Type::Generic(
+                                    "Vec".to_string(),
+                                    vec![at(
+                                        Type::Array(Box::new(at(
+                                            Type::Atomic("i32".to_string()),
+                                            17,
+                                            20
+                                        ))),
+                                        16,
+                                        21
+                                    )]
+                                ),
+                                12,
+                                22
+                            ),
+                        ]
+                    ),
+                    0,
+                    23
                 ),
                 ""
             ))
-        )
-    }
-
-    #[test]
-    fn parse_instantiate() {
+        );
         assert_eq!(
-            instantiate().parse("let a: i32 = 50"),
+            types().parse("Vec<i32").unwrap_err().to_string(),
+            "1:8: expected `>`, found end of input"
+        );
+        assert_eq!(
+            statement()
+                .parse("let t: Vec<> = 1")
+                .unwrap_err()
+                .to_string(),
+            "1:12: expected type, found `>`"
+        );
+        assert_eq!(
+            instantiate().parse("let x: Vec<bool> = a < b"),
+            Ok((
+                Statement::Instantiate(
+                    Some(at(
+                        Type::Generic(
+                            "Vec".to_string(),
+                            vec![at(Type::Atomic("bool".to_string()), 11, 15)]
+                        ),
+                        7,
+                        16
+                    )),
+                    binding_at("x", 4),
+                    binary(
+                        BinaryOperator::Less,
+                        reference_at("a", 19),
+                        reference_at("b", 23)
+                    )
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_spanned_types() {
+        assert_eq!(
+            types().parse(" [ [i32] ] "),
+            Ok((
+                at(
+                    Type::Array(Box::new(at(
+                        Type::Array(Box::new(at(Type::Atomic("i32".to_string()), 4, 7))),
+                        3,
+                        8
+                    ))),
+                    1,
+                    10
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn type_errors() {
+        assert_eq!(
+            types().parse("1").unwrap_err().to_string(),
+            "1:1: expected type, found `1`"
+        );
+        assert_eq!(
+            types().parse("[1]").unwrap_err().to_string(),
+            "1:2: expected type, found `1`"
+        );
+    }
+
+    #[test]
+    fn parse_float_literal() {
+        assert_eq!(
+            float_literal().parse("12378.5"),
+            Ok((Literal::Float("12378.5".to_string(), None), ""))
+        )
+    }
+
+    #[test]
+    fn parse_integer_literal() {
+        assert_eq!(
+            integer_literal().parse("12378"),
+            Ok((Literal::Integer("12378".to_string(), None), ""))
+        )
+    }
+
+    #[test]
+    fn parse_suffixed_literals() {
+        assert_eq!(
+            literal().parse("10u8").map(|(literal, _)| literal.node),
+            Ok(Literal::Integer("10".to_string(), Some(IntegerSuffix::U8)))
+        );
+        assert_eq!(
+            literal().parse("0xffi64").map(|(literal, _)| literal.node),
+            Ok(Literal::Integer(
+                "0xff".to_string(),
+                Some(IntegerSuffix::I64)
+            ))
+        );
+        assert_eq!(
+            literal().parse("2.0f64").map(|(literal, _)| literal.node),
+            Ok(Literal::Float("2.0".to_string(), Some(FloatSuffix::F64)))
+        );
+        assert_eq!(
+            literal().parse("1.5e-3").map(|(literal, _)| literal.node),
+            Ok(Literal::Float("1.5e-3".to_string(), None))
+        );
+        assert_eq!(
+            literal().parse("12.3fff").unwrap_err().to_string(),
+            "1:5: invalid suffix `fff` for float literal"
+        );
+        assert_eq!(
+            literal().parse("7px").unwrap_err().to_string(),
+            "1:2: invalid suffix `px` for integer literal"
+        );
+        assert_eq!(
+            literal().parse("1f32").map(|(literal, _)| literal.node),
+            Ok(Literal::Float("1".to_string(), Some(FloatSuffix::F32)))
+        );
+        assert_eq!(
+            literal().parse("0x_ff_u8").map(|(literal, _)| literal.node),
+            Ok(Literal::Integer(
+                "0xff".to_string(),
+                Some(IntegerSuffix::U8)
+            ))
+        );
+        assert_eq!(
+            literal().parse("0b1f64").unwrap_err().to_string(),
+            "1:1: invalid suffix `f64` for integer literal"
+        );
+        assert_eq!(
+            literal().parse("0x_").unwrap_err().to_string(),
+            "1:4: expected hex digit, found end of input"
+        );
+    }
+
+    #[test]
+    fn parse_boolean_literal() {
+        assert_eq!(
+            bool_literal().parse("true"),
+            Ok((Literal::Boolean(true), ""))
+        )
+    }
+
+    #[test]
+    fn parse_text_literals() {
+        assert_eq!(
+            string_literal().parse(r#""hi\n""#),
+            Ok((Literal::String("hi\n".to_string()), ""))
+        );
+        assert_eq!(char_literal().parse(r"'\t'"), Ok((Literal::Char('\t'), "")));
+    }
+
+    #[test]
+    fn parse_reference_literal() {
+        assert_eq!(
+            reference_literal().parse("jlfd3"),
+            Ok((Literal::Reference("jlfd3".to_string()), ""))
+        )
+    }
+
+    #[test]
+    fn literal_errors() {
+        assert_eq!(
+            literal().parse("+").unwrap_err().to_string(),
+            "1:1: expected float, integer, `true`, `false`, string, character or identifier, found `+`"
+        );
+        assert_eq!(
+            expression().parse(" +").unwrap_err().to_string(),
+            "1:2: expected expression, found `+`"
+        );
+    }
+
+    #[test]
+    fn parse_literal_expr() {
+        assert_eq!(
+            literal_expr().parse("123"),
+            Ok((
+                Expression::Literal(at(Literal::Integer("123".to_string(), None), 0, 3)),
+                ""
+            ))
+        )
+    }
+
+    fn binary(
+        operator: BinaryOperator,
+        left: Spanned<Expression>,
+        right: Spanned<Expression>,
+    ) -> Spanned<Expression> {
+        let span = Span::new(left.span.start, right.span.end);
+        Spanned::new(
+            Expression::Binary(operator, Box::new(left), Box::new(right)),
+            span,
+        )
+    }
+
+    fn binding_at(name: &str, start: usize) -> Spanned<Pattern> {
+        at(
+            Pattern::Binding(name.to_string()),
+            start,
+            start + name.len(),
+        )
+    }
+
+    fn reference_at(name: &str, start: usize) -> Spanned<Expression> {
+        let end = start + name.len();
+        at(
+            Expression::Literal(at(Literal::Reference(name.to_string()), start, end)),
+            start,
+            end,
+        )
+    }
+
+    #[test]
+    fn parse_binary_expr() {
+        assert_eq!(
+            expression().parse("a + b * 2"),
+            Ok((
+                binary(
+                    BinaryOperator::Add,
+                    reference_at("a", 0),
+                    binary(
+                        BinaryOperator::Multiply,
+                        reference_at("b", 4),
+                        integer_at("2", 8)
+                    )
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("a - b - c"),
+            Ok((
+                binary(
+                    BinaryOperator::Subtract,
+                    binary(
+                        BinaryOperator::Subtract,
+                        reference_at("a", 0),
+                        reference_at("b", 4)
+                    ),
+                    reference_at("c", 8)
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("a < b || c & d == 1 << 2"),
+            Ok((
+                binary(
+                    BinaryOperator::Or,
+                    binary(
+                        BinaryOperator::Less,
+                        reference_at("a", 0),
+                        reference_at("b", 4)
+                    ),
+                    binary(
+                        BinaryOperator::Equal,
+                        binary(
+                            BinaryOperator::BitAnd,
+                            reference_at("c", 9),
+                            reference_at("d", 13)
+                        ),
+                        binary(
+                            BinaryOperator::ShiftLeft,
+                            integer_at("1", 18),
+                            integer_at("2", 23)
+                        )
+                    )
+                ),
+                ""
+            ))
+        );
+    }
+
+    fn unary(
+        operator: UnaryOperator,
+        operand: Spanned<Expression>,
+        start: usize,
+    ) -> Spanned<Expression> {
+        let end = operand.span.end;
+        at(Expression::Unary(operator, Box::new(operand)), start, end)
+    }
+
+    #[test]
+    fn parse_unary_expr() {
+        assert_eq!(
+            expression().parse("1 < 2 < 3").unwrap_err().to_string(),
+            "1:7: non-associative operators cannot be chained"
+        );
+        assert_eq!(
+            expression().parse("a < b > c").unwrap_err().to_string(),
+            "1:7: non-associative operators cannot be chained"
+        );
+        assert_eq!(
+            expression().parse("(a < b) == c").map(|(_, rest)| rest),
+            Ok("")
+        );
+        assert_eq!(
+            expression().parse("-a * b"),
+            Ok((
+                binary(
+                    BinaryOperator::Multiply,
+                    unary(UnaryOperator::Negate, reference_at("a", 1), 0),
+                    reference_at("b", 5)
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("!~x"),
+            Ok((
+                unary(
+                    UnaryOperator::Not,
+                    unary(UnaryOperator::BitNot, reference_at("x", 2), 1),
+                    0
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_group_expr() {
+        assert_eq!(
+            expression().parse("-(a + 1) * b"),
+            Ok((
+                binary(
+                    BinaryOperator::Multiply,
+                    at(
+                        Expression::Unary(
+                            UnaryOperator::Negate,
+                            Box::new(at(
+                                Expression::Binary(
+                                    BinaryOperator::Add,
+                                    Box::new(reference_at("a", 2)),
+                                    Box::new(integer_at("1", 6))
+                                ),
+                                1,
+                                8
+                            ))
+                        ),
+                        0,
+                        8
+                    ),
+                    reference_at("b", 11)
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("(1").unwrap_err().to_string(),
+            "1:3: expected `)` or `,`, found end of input"
+        );
+        assert_eq!(
+            expression().parse("(,)").unwrap_err().to_string(),
+            "1:2: expected expression or `)`, found `,`"
+        );
+        assert_eq!(
+            expression().parse("( )"),
+            Ok((at(Expression::Tuple(vec![]), 0, 3), ""))
+        );
+    }
+
+    #[test]
+    fn group_spans_include_parentheses() {
+        assert_eq!(
+            expression().parse("(a + b) * c").map(|(expr, _)| expr.span),
+            Ok(Span::new(0, 11))
+        );
+        assert_eq!(
+            expression()
+                .parse("(a + b) * c")
+                .map(|(expr, _)| match expr.node {
+                    Expression::Binary(_, left, _) => left.span,
+                    _ => unreachable!(),
+                }),
+            Ok(Span::new(0, 7))
+        );
+    }
+
+    #[test]
+    fn invalid_tuple_indices() {
+        assert_eq!(
+            statement().parse("t.0_1").unwrap_err().to_string(),
+            "1:3: invalid tuple index `0_1`"
+        );
+        assert_eq!(
+            statement().parse("t.00").unwrap_err().to_string(),
+            "1:3: invalid tuple index `00`"
+        );
+        assert_eq!(
+            statement().parse("t.01").unwrap_err().to_string(),
+            "1:3: invalid tuple index `01`"
+        );
+    }
+
+    #[test]
+    fn parse_tuple_expr() {
+        assert_eq!(
+            expression().parse("(1, true).1"),
+            Ok((
+                at(
+                    Expression::Element {
+                        target: Box::new(at(
+                            Expression::Tuple(vec![
+                                integer_at("1", 1),
+                                at(Expression::Literal(at(Literal::Boolean(true), 4, 8)), 4, 8),
+                            ]),
+                            0,
+                            9
+                        )),
+                        index: 1,
+                    },
+                    0,
+                    11
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("(a,)"),
+            Ok((at(Expression::Tuple(vec![reference_at("a", 1)]), 0, 4), ""))
+        );
+        assert_eq!(
+            expression().parse("t.0.1"),
+            Ok((
+                at(
+                    Expression::Element {
+                        target: Box::new(at(
+                            Expression::Element {
+                                target: Box::new(reference_at("t", 0)),
+                                index: 0,
+                            },
+                            0,
+                            3
+                        )),
+                        index: 1,
+                    },
+                    0,
+                    5
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("t.0 + 1.5"),
+            Ok((
+                binary(
+                    BinaryOperator::Add,
+                    at(
+                        Expression::Element {
+                            target: Box::new(reference_at("t", 0)),
+                            index: 0,
+                        },
+                        0,
+                        3
+                    ),
+                    at(
+                        Expression::Literal(at(Literal::Float("1.5".to_string(), None), 6, 9)),
+                        6,
+                        9
+                    )
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_call_expr() {
+        assert_eq!(
+            expression().parse("f(a, 1)"),
+            Ok((
+                at(
+                    Expression::Call {
+                        callee: Box::new(reference_at("f", 0)),
+                        args: vec![reference_at("a", 2), integer_at("1", 5)],
+                    },
+                    0,
+                    7
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("f(a,)"),
+            Ok((
+                at(
+                    Expression::Call {
+                        callee: Box::new(reference_at("f", 0)),
+                        args: vec![reference_at("a", 2)],
+                    },
+                    0,
+                    5
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("f(a b)").unwrap_err().to_string(),
+            "1:5: expected `)` or `,`, found `b`"
+        );
+        assert_eq!(
+            expression().parse("-g()(2)"),
+            Ok((
+                unary(
+                    UnaryOperator::Negate,
+                    at(
+                        Expression::Call {
+                            callee: Box::new(at(
+                                Expression::Call {
+                                    callee: Box::new(reference_at("g", 1)),
+                                    args: vec![],
+                                },
+                                1,
+                                4
+                            )),
+                            args: vec![integer_at("2", 5)],
+                        },
+                        1,
+                        7
+                    ),
+                    0
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_method_call_expr() {
+        assert_eq!(
+            expression().parse("x.push(1).len() + 1"),
+            Ok((
+                binary(
+                    BinaryOperator::Add,
+                    at(
+                        Expression::MethodCall {
+                            receiver: Box::new(at(
+                                Expression::MethodCall {
+                                    receiver: Box::new(reference_at("x", 0)),
+                                    method: "push".to_string(),
+                                    args: vec![integer_at("1", 7)],
+                                },
+                                0,
+                                9
+                            )),
+                            method: "len".to_string(),
+                            args: vec![],
+                        },
+                        0,
+                        15
+                    ),
+                    integer_at("1", 18)
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_array_expr() {
+        assert_eq!(
+            expression().parse("[1, 2, 3]"),
+            Ok((
+                at(
+                    Expression::Array(ArrayElements::List(vec![
+                        integer_at("1", 1),
+                        integer_at("2", 4),
+                        integer_at("3", 7),
+                    ])),
+                    0,
+                    9
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("[0; 16]"),
+            Ok((
+                at(
+                    Expression::Array(ArrayElements::Repeat(
+                        Box::new(integer_at("0", 1)),
+                        Box::new(integer_at("16", 4))
+                    )),
+                    0,
+                    7
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("[]"),
+            Ok((at(Expression::Array(ArrayElements::List(vec![])), 0, 2), ""))
+        );
+        assert_eq!(
+            expression().parse("[0; ]").unwrap_err().to_string(),
+            "1:5: expected expression, found `]`"
+        );
+        assert_eq!(
+            expression().parse("[1, 2,]"),
+            Ok((
+                at(
+                    Expression::Array(ArrayElements::List(vec![
+                        integer_at("1", 1),
+                        integer_at("2", 4),
+                    ])),
+                    0,
+                    7
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("[1,,]").unwrap_err().to_string(),
+            "1:4: expected expression or `]`, found `,`"
+        );
+    }
+
+    #[test]
+    fn parse_index_expr() {
+        assert_eq!(
+            expression().parse("a[i][0] + 1"),
+            Ok((
+                binary(
+                    BinaryOperator::Add,
+                    at(
+                        Expression::Index {
+                            target: Box::new(at(
+                                Expression::Index {
+                                    target: Box::new(reference_at("a", 0)),
+                                    index: Box::new(reference_at("i", 2)),
+                                },
+                                0,
+                                4
+                            )),
+                            index: Box::new(integer_at("0", 5)),
+                        },
+                        0,
+                        7
+                    ),
+                    integer_at("1", 10)
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_if_expr() {
+        assert_eq!(
+            expression().parse("if a { } else if b { } else { 1; }"),
+            Ok((
+                at(
+                    Expression::If {
+                        condition: Box::new(reference_at("a", 3)),
+                        then_branch: Box::new(at(Expression::Block(vec![]), 5, 8)),
+                        else_branch: Some(Box::new(at(
+                            Expression::If {
+                                condition: Box::new(reference_at("b", 17)),
+                                then_branch: Box::new(at(Expression::Block(vec![]), 19, 22)),
+                                else_branch: Some(Box::new(at(
+                                    Expression::Block(vec![at(
+                                        Statement::Expression(integer_at("1", 30)),
+                                        30,
+                                        31
+                                    )]),
+                                    28,
+                                    34
+                                ))),
+                            },
+                            14,
+                            34
+                        ))),
+                    },
+                    0,
+                    34
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_if_instantiate() {
+        assert_eq!(
+            statement().parse("let x = if a < 2 { } + 1"),
+            Ok((
+                at(
+                    Statement::Instantiate(
+                        None,
+                        binding_at("x", 4),
+                        binary(
+                            BinaryOperator::Add,
+                            at(
+                                Expression::If {
+                                    condition: Box::new(binary(
+                                        BinaryOperator::Less,
+                                        reference_at("a", 11),
+                                        integer_at("2", 15)
+                                    )),
+                                    then_branch: Box::new(at(Expression::Block(vec![]), 17, 20)),
+                                    else_branch: None,
+                                },
+                                8,
+                                20
+                            ),
+                            integer_at("1", 23)
+                        )
+                    ),
+                    0,
+                    24
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            statement().parse("let x = if a 1").unwrap_err().to_string(),
+            "1:14: expected `{`, found `1`"
+        );
+        assert_eq!(
+            statement()
+                .parse("if a { } else 1")
+                .unwrap_err()
+                .to_string(),
+            "1:15: expected `if` or `{`, found `1`"
+        );
+    }
+
+    #[test]
+    fn parse_comparison_statement() {
+        assert_eq!(
+            statement().parse("a == 1"),
+            Ok((
+                at(
+                    Statement::Expression(binary(
+                        BinaryOperator::Equal,
+                        reference_at("a", 0),
+                        integer_at("1", 5)
+                    )),
+                    0,
+                    6
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_block_expr() {
+        assert_eq!(
+            block_expr().parse("{let a = 123;}"),
+            Ok((
+                Expression::Block(vec![at(
+                    Statement::Instantiate(None, binding_at("a", 5), integer_at("123", 9)),
+                    1,
+                    12
+                )]),
+                ""
+            ))
+        )
+    }
+
+    #[test]
+    fn parse_declare() {
+        assert_eq!(
+            declare().parse("let a: int"),
+            Ok((
+                Statement::Declare(
+                    Some(at(Type::Atomic("int".to_string()), 7, 10)),
+                    "a".to_string()
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            declare().parse("let a"),
+            Ok((Statement::Declare(None, "a".to_string()), ""))
+        );
+    }
+
+    #[test]
+    fn parse_assignment() {
+        assert_eq!(
+            expression_stat().parse("a = 50"),
+            Ok((
+                Statement::Assign(reference_at("a", 0), None, integer_at("50", 4)),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression_stat().parse("a[i] = 2"),
+            Ok((
+                Statement::Assign(
+                    at(
+                        Expression::Index {
+                            target: Box::new(reference_at("a", 0)),
+                            index: Box::new(reference_at("i", 2)),
+                        },
+                        0,
+                        4
+                    ),
+                    None,
+                    integer_at("2", 7)
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression_stat().parse("f() = 2").unwrap_err().to_string(),
+            "1:1: invalid assignment target"
+        );
+    }
+
+    #[test]
+    fn invalid_assignment_targets() {
+        assert_eq!(
+            statement().parse("a.b() = 1").unwrap_err().to_string(),
+            "1:1: invalid assignment target"
+        );
+        assert_eq!(
+            crate::program::parse_program("let a = 1;\n  (a + 1) += 2;")
+                .unwrap_err()
+                .errors[0]
+                .to_string(),
+            "2:3: invalid assignment target"
+        );
+        assert_eq!(
+            crate::program::parse_program("{ f() = 1; };")
+                .unwrap_err()
+                .errors[0]
+                .to_string(),
+            "1:3: invalid assignment target"
+        );
+    }
+
+    #[test]
+    fn parse_compound_assignment() {
+        assert_eq!(
+            expression_stat().parse("p.x += 1"),
+            Ok((
+                Statement::Assign(
+                    at(
+                        Expression::Field {
+                            target: Box::new(reference_at("p", 0)),
+                            field: "x".to_string(),
+                        },
+                        0,
+                        3
+                    ),
+                    Some(BinaryOperator::Add),
+                    integer_at("1", 7)
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression_stat().parse("a <<= 2"),
+            Ok((
+                Statement::Assign(
+                    reference_at("a", 0),
+                    Some(BinaryOperator::ShiftLeft),
+                    integer_at("2", 6)
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            statement().parse("a <= 2"),
+            Ok((
+                at(
+                    Statement::Expression(binary(
+                        BinaryOperator::LessEqual,
+                        reference_at("a", 0),
+                        integer_at("2", 5)
+                    )),
+                    0,
+                    6
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_instantiate() {
+        assert_eq!(
+            instantiate().parse("let a: i32 = 50"),
+            Ok((
+                Statement::Instantiate(
+                    Some(at(Type::Atomic("i32".to_string()), 7, 10)),
+                    binding_at("a", 4),
+                    integer_at("50", 13)
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            instantiate().parse("let a = 50"),
+            Ok((
+                Statement::Instantiate(None, binding_at("a", 4), integer_at("50", 8)),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_function() {
+        assert_eq!(
+            function().parse("fn f(a: i32, b: [f32]) -> bool { a; }"),
+            Ok((
+                Statement::Function {
+                    name: "f".to_string(),
+                    generics: vec![],
+                    parameters: vec![
+                        at(
+                            Parameter {
+                                name: "a".to_string(),
+                                annotation: at(Type::Atomic("i32".to_string()), 8, 11),
+                            },
+                            5,
+                            11
+                        ),
+                        at(
+                            Parameter {
+                                name: "b".to_string(),
+                                annotation: at(
+                                    Type::Array(Box::new(at(
+                                        Type::Atomic("f32".to_string()),
+                                        17,
+                                        20
+                                    ))),
+                                    16,
+                                    21
+                                ),
+                            },
+                            13,
+                            21
+                        ),
+                    ],
+                    output: Some(at(Type::Atomic("bool".to_string()), 26, 30)),
+                    body: at(
+                        Expression::Block(vec![at(
+                            Statement::Expression(reference_at("a", 33)),
+                            33,
+                            34
+                        )]),
+                        31,
+                        37
+                    ),
+                },
+                ""
+            ))
+        );
+        assert_eq!(
+            function().parse("fn f(a: i32,) {}"),
+            Ok((
+                Statement::Function {
+                    name: "f".to_string(),
+                    generics: vec![],
+                    parameters: vec![at(
+                        Parameter {
+                            name: "a".to_string(),
+                            annotation: at(Type::Atomic("i32".to_string()), 8, 11),
+                        },
+                        5,
+                        11
+                    )],
+                    output: None,
+                    body: at(Expression::Block(vec![]), 14, 16),
+                },
+                ""
+            ))
+        );
+        assert_eq!(
+            function().parse("fn main() {}"),
+            Ok((
+                Statement::Function {
+                    name: "main".to_string(),
+                    generics: vec![],
+                    parameters: vec![],
+                    output: None,
+                    body: at(Expression::Block(vec![]), 10, 12),
+                },
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn function_errors() {
+        assert_eq!(
+            statement()
+                .parse("fn f(a: i32) i32 {}")
+                .unwrap_err()
+                .to_string(),
+            "1:14: expected `{`, found `i`"
+        );
+        assert_eq!(
+            statement().parse("fn f(a: ) {}").unwrap_err().to_string(),
+            "1:9: expected type, found `)`"
+        );
+    }
+
+    #[test]
+    fn parse_loops() {
+        assert_eq!(
+            statement().parse("while a { continue; }"),
+            Ok((
+                at(
+                    Statement::While {
+                        condition: reference_at("a", 6),
+                        body: at(
+                            Expression::Block(vec![at(Statement::Continue, 10, 18)]),
+                            8,
+                            21
+                        ),
+                    },
+                    0,
+                    21
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            statement().parse("for x in xs { break; }"),
+            Ok((
+                at(
+                    Statement::For {
+                        binding: "x".to_string(),
+                        iterable: reference_at("xs", 9),
+                        body: at(
+                            Expression::Block(vec![at(Statement::Break(None), 14, 19)]),
+                            12,
+                            22
+                        ),
+                    },
+                    0,
+                    22
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            statement().parse("let a = loop { break 1; }"),
+            Ok((
+                at(
+                    Statement::Instantiate(
+                        None,
+                        binding_at("a", 4),
+                        at(
+                            Expression::Loop(Box::new(at(
+                                Expression::Block(vec![at(
+                                    Statement::Break(Some(integer_at("1", 21))),
+                                    15,
+                                    22
+                                )]),
+                                13,
+                                25
+                            ))),
+                            8,
+                            25
+                        )
+                    ),
+                    0,
+                    25
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            statement().parse("for x xs {}").unwrap_err().to_string(),
+            "1:7: expected `in`, found `x`"
+        );
+    }
+
+    #[test]
+    fn committed_statements() {
+        assert_eq!(
+            statement().parse("let a: = 5").unwrap_err().to_string(),
+            "1:8: expected type, found `=`"
+        );
+        assert_eq!(
+            statement().parse("let = 5").unwrap_err().to_string(),
+            "1:5: expected pattern, found `=`"
+        );
+        assert_eq!(
+            program()
+                .parse("let a = 1;\n{ b = ; }")
+                .unwrap_err()
+                .to_string(),
+            "2:7: expected expression, found `;`"
+        );
+    }
+
+    #[test]
+    fn parse_generic_items() {
+        let t_at = |start| at(Type::Atomic("T".to_string()), start, start + 1);
+
+        assert_eq!(
+            function().parse("fn id<T: Show + Eq, U>(x: T) -> T { x; }"),
             Ok((
-                Statement::Instantiate(
-                    Some(Type::Atomic("i32".to_string())),
-                    "a".to_string(),
-                    Expression::Literal(Literal::Integer("50".to_string()))
+                Statement::Function {
+                    name: "id".to_string(),
+                    generics: vec![
+                        at(
+                            GenericParameter {
+                                name: "T".to_string(),
+                                bounds: vec![
+                                    at(Type::Atomic("Show".to_string()), 9, 13),
+                                    at(Type::Atomic("Eq".to_string()), 16, 18),
+                                ],
+                            },
+                            6,
+                            18
+                        ),
+                        at(
+                            GenericParameter {
+                                name: "U".to_string(),
+                                bounds: vec![],
+                            },
+                            20,
+                            21
+                        ),
+                    ],
+                    parameters: vec![at(
+                        Parameter {
+                            name: "x".to_string(),
+                            annotation: t_at(26),
+                        },
+                        23,
+                        27
+                    )],
+                    output: Some(t_at(32)),
+                    body: at(
+                        Expression::Block(vec![at(
+                            Statement::Expression(reference_at("x", 36)),
+                            36,
+                            37
+                        )]),
+                        34,
+                        40
+                    ),
+                },
+                ""
+            ))
+        );
+        assert_eq!(
+            statement().parse("struct Box<T> { value: T }"),
+            Ok((
+                at(
+                    Statement::Struct {
+                        name: "Box".to_string(),
+                        generics: vec![at(
+                            GenericParameter {
+                                name: "T".to_string(),
+                                bounds: vec![],
+                            },
+                            11,
+                            12
+                        )],
+                        fields: vec![at(
+                            StructField {
+                                name: "value".to_string(),
+                                annotation: t_at(23),
+                            },
+                            16,
+                            24
+                        )],
+                    },
+                    0,
+                    26
                 ),
                 ""
             ))
         );
         assert_eq!(
-            instantiate().parse("let a = 50"),
+            statement().parse("fn f<T:>() {}").unwrap_err().to_string(),
+            "1:8: expected type, found `>`"
+        );
+        assert_eq!(
+            statement().parse("fn f<>() {}").unwrap_err().to_string(),
+            "1:6: expected identifier, found `>`"
+        );
+        assert_eq!(
+            statement().parse("struct S<> {}").unwrap_err().to_string(),
+            "1:10: expected identifier, found `>`"
+        );
+    }
+
+    #[test]
+    fn parse_structs() {
+        assert_eq!(
+            statement().parse("struct Point { x: f32, y: f32, }"),
+            Ok((
+                at(
+                    Statement::Struct {
+                        name: "Point".to_string(),
+                        generics: vec![],
+                        fields: vec![
+                            at(
+                                StructField {
+                                    name: "x".to_string(),
+                                    annotation: at(Type::Atomic("f32".to_string()), 18, 21),
+                                },
+                                15,
+                                21
+                            ),
+                            at(
+                                StructField {
+                                    name: "y".to_string(),
+                                    annotation: at(Type::Atomic("f32".to_string()), 26, 29),
+                                },
+                                23,
+                                29
+                            ),
+                        ],
+                    },
+                    0,
+                    32
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("Point { x: 1, y: 2 }.x"),
+            Ok((
+                at(
+                    Expression::Field {
+                        target: Box::new(at(
+                            Expression::Struct {
+                                name: "Point".to_string(),
+                                fields: vec![
+                                    at(
+                                        FieldValue {
+                                            name: "x".to_string(),
+                                            value: integer_at("1", 11),
+                                        },
+                                        8,
+                                        12
+                                    ),
+                                    at(
+                                        FieldValue {
+                                            name: "y".to_string(),
+                                            value: integer_at("2", 17),
+                                        },
+                                        14,
+                                        18
+                                    ),
+                                ],
+                            },
+                            0,
+                            20
+                        )),
+                        field: "x".to_string(),
+                    },
+                    0,
+                    22
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            statement().parse("while done {}"),
+            Ok((
+                at(
+                    Statement::While {
+                        condition: reference_at("done", 6),
+                        body: at(Expression::Block(vec![]), 11, 13),
+                    },
+                    0,
+                    13
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            statement().parse("struct P { x }").unwrap_err().to_string(),
+            "1:14: expected `:`, found `}`"
+        );
+    }
+
+    #[test]
+    fn parse_enums() {
+        let f32_at = |start| at(Type::Atomic("f32".to_string()), start, start + 3);
+
+        assert_eq!(
+            statement().parse("enum Shape { Circle(f32), Rect { w: f32 }, Empty }"),
+            Ok((
+                at(
+                    Statement::Enum {
+                        name: "Shape".to_string(),
+                        variants: vec![
+                            at(
+                                EnumVariant {
+                                    name: "Circle".to_string(),
+                                    fields: VariantFields::Tuple(vec![f32_at(20)]),
+                                },
+                                13,
+                                24
+                            ),
+                            at(
+                                EnumVariant {
+                                    name: "Rect".to_string(),
+                                    fields: VariantFields::Struct(vec![at(
+                                        StructField {
+                                            name: "w".to_string(),
+                                            annotation: f32_at(36),
+                                        },
+                                        33,
+                                        39
+                                    )]),
+                                },
+                                26,
+                                41
+                            ),
+                            at(
+                                EnumVariant {
+                                    name: "Empty".to_string(),
+                                    fields: VariantFields::Unit,
+                                },
+                                43,
+                                48
+                            ),
+                        ],
+                    },
+                    0,
+                    50
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("Shape::Circle(1)"),
+            Ok((
+                at(
+                    Expression::Variant {
+                        name: "Shape".to_string(),
+                        variant: "Circle".to_string(),
+                        values: VariantValues::Tuple(vec![integer_at("1", 14)]),
+                    },
+                    0,
+                    16
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("Shape::Rect { w: 2 }"),
+            Ok((
+                at(
+                    Expression::Variant {
+                        name: "Shape".to_string(),
+                        variant: "Rect".to_string(),
+                        values: VariantValues::Struct(vec![at(
+                            FieldValue {
+                                name: "w".to_string(),
+                                value: integer_at("2", 17),
+                            },
+                            14,
+                            18
+                        )]),
+                    },
+                    0,
+                    20
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            statement().parse("if s == Shape::Empty {}"),
+            Ok((
+                at(
+                    Statement::Expression(at(
+                        Expression::If {
+                            condition: Box::new(binary(
+                                BinaryOperator::Equal,
+                                reference_at("s", 3),
+                                at(
+                                    Expression::Variant {
+                                        name: "Shape".to_string(),
+                                        variant: "Empty".to_string(),
+                                        values: VariantValues::Unit,
+                                    },
+                                    8,
+                                    20
+                                )
+                            )),
+                            then_branch: Box::new(at(Expression::Block(vec![]), 21, 23)),
+                            else_branch: None,
+                        },
+                        0,
+                        23
+                    )),
+                    0,
+                    23
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            statement().parse("enum Shape { Circle(f32,), }"),
+            Ok((
+                at(
+                    Statement::Enum {
+                        name: "Shape".to_string(),
+                        variants: vec![at(
+                            EnumVariant {
+                                name: "Circle".to_string(),
+                                fields: VariantFields::Tuple(vec![f32_at(20)]),
+                            },
+                            13,
+                            25
+                        )],
+                    },
+                    0,
+                    28
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("Shape::Circle(1.0,)"),
+            Ok((
+                at(
+                    Expression::Variant {
+                        name: "Shape".to_string(),
+                        variant: "Circle".to_string(),
+                        values: VariantValues::Tuple(vec![at(
+                            Expression::Literal(at(
+                                Literal::Float("1.0".to_string(), None),
+                                14,
+                                17
+                            )),
+                            14,
+                            17
+                        )]),
+                    },
+                    0,
+                    19
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("Shape::").unwrap_err().to_string(),
+            "1:8: expected identifier, found end of input"
+        );
+    }
+
+    #[test]
+    fn parse_patterns() {
+        assert_eq!(
+            pattern().parse("Shape::Rect { w, h: _ }"),
+            Ok((
+                at(
+                    Pattern::Variant {
+                        name: "Shape".to_string(),
+                        variant: "Rect".to_string(),
+                        values: VariantPatterns::Struct(vec![
+                            at(
+                                FieldPattern {
+                                    name: "w".to_string(),
+                                    pattern: None,
+                                },
+                                14,
+                                15
+                            ),
+                            at(
+                                FieldPattern {
+                                    name: "h".to_string(),
+                                    pattern: Some(at(Pattern::Wildcard, 20, 21)),
+                                },
+                                17,
+                                21
+                            ),
+                        ]),
+                    },
+                    0,
+                    23
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            pattern().parse("((a), 'x', 1)"),
+            Ok((
+                at(
+                    Pattern::Tuple(vec![
+                        at(Pattern::Binding("a".to_string()), 1, 4),
+                        at(Pattern::Literal(at(Literal::Char('x'), 6, 9)), 6, 9),
+                        at(
+                            Pattern::Literal(at(Literal::Integer("1".to_string(), None), 11, 12)),
+                            11,
+                            12
+                        ),
+                    ]),
+                    0,
+                    13
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            instantiate().parse("let (a, b) = t"),
             Ok((
                 Statement::Instantiate(
                     None,
-                    "a".to_string(),
-                    Expression::Literal(Literal::Integer("50".to_string()))
+                    at(
+                        Pattern::Tuple(vec![binding_at("a", 5), binding_at("b", 8)]),
+                        4,
+                        10
+                    ),
+                    reference_at("t", 13)
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            statement()
+                .parse("let Some::X(a) = t")
+                .unwrap_err()
+                .to_string(),
+            "1:5: refutable pattern in `let` binding"
+        );
+    }
+
+    #[test]
+    fn parse_match_expr() {
+        assert_eq!(
+            expression().parse("match s { Shape::Circle(r) if r > 0 => r, _ => 0, }"),
+            Ok((
+                at(
+                    Expression::Match {
+                        scrutinee: Box::new(reference_at("s", 6)),
+                        arms: vec![
+                            at(
+                                MatchArm {
+                                    pattern: at(
+                                        Pattern::Variant {
+                                            name: "Shape".to_string(),
+                                            variant: "Circle".to_string(),
+                                            values: VariantPatterns::Tuple(vec![binding_at(
+                                                "r", 24
+                                            )]),
+                                        },
+                                        10,
+                                        26
+                                    ),
+                                    guard: Some(binary(
+                                        BinaryOperator::Greater,
+                                        reference_at("r", 30),
+                                        integer_at("0", 34)
+                                    )),
+                                    body: reference_at("r", 39),
+                                },
+                                10,
+                                40
+                            ),
+                            at(
+                                MatchArm {
+                                    pattern: at(Pattern::Wildcard, 42, 43),
+                                    guard: None,
+                                    body: integer_at("0", 47),
+                                },
+                                42,
+                                48
+                            ),
+                        ],
+                    },
+                    0,
+                    51
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("match x { -1 => 0, - 2.5 => 1 }"),
+            Ok((
+                at(
+                    Expression::Match {
+                        scrutinee: Box::new(reference_at("x", 6)),
+                        arms: vec![
+                            at(
+                                MatchArm {
+                                    pattern: at(
+                                        Pattern::Negative(at(
+                                            Literal::Integer("1".to_string(), None),
+                                            11,
+                                            12
+                                        )),
+                                        10,
+                                        12
+                                    ),
+                                    guard: None,
+                                    body: integer_at("0", 16),
+                                },
+                                10,
+                                17
+                            ),
+                            at(
+                                MatchArm {
+                                    pattern: at(
+                                        Pattern::Negative(at(
+                                            Literal::Float("2.5".to_string(), None),
+                                            21,
+                                            24
+                                        )),
+                                        19,
+                                        24
+                                    ),
+                                    guard: None,
+                                    body: integer_at("1", 28),
+                                },
+                                19,
+                                29
+                            ),
+                        ],
+                    },
+                    0,
+                    31
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("match x { 1 => {} 2 => b }"),
+            Ok((
+                at(
+                    Expression::Match {
+                        scrutinee: Box::new(reference_at("x", 6)),
+                        arms: vec![
+                            at(
+                                MatchArm {
+                                    pattern: at(
+                                        Pattern::Literal(at(
+                                            Literal::Integer("1".to_string(), None),
+                                            10,
+                                            11
+                                        )),
+                                        10,
+                                        11
+                                    ),
+                                    guard: None,
+                                    body: at(Expression::Block(vec![]), 15, 17),
+                                },
+                                10,
+                                17
+                            ),
+                            at(
+                                MatchArm {
+                                    pattern: at(
+                                        Pattern::Literal(at(
+                                            Literal::Integer("2".to_string(), None),
+                                            18,
+                                            19
+                                        )),
+                                        18,
+                                        19
+                                    ),
+                                    guard: None,
+                                    body: reference_at("b", 23),
+                                },
+                                18,
+                                24
+                            ),
+                        ],
+                    },
+                    0,
+                    26
                 ),
                 ""
             ))
         );
+        assert_eq!(
+            expression()
+                .parse("match x { 1 => a 2 => b }")
+                .unwrap_err()
+                .to_string(),
+            "1:18: expected `,` or `}`, found `2`"
+        );
+        assert_eq!(
+            expression()
+                .parse("match x { -a => 0 }")
+                .unwrap_err()
+                .to_string(),
+            "1:12: expected float or integer, found `a`"
+        );
+        assert_eq!(
+            expression()
+                .parse("match x { 1 2 }")
+                .unwrap_err()
+                .to_string(),
+            "1:13: expected `=>`, found `2`"
+        );
+    }
+
+    #[test]
+    fn parse_statements_without_separator() {
+        assert_eq!(
+            statement().many().parse("let a  "),
+            Ok((
+                vec![at(Statement::Declare(None, "a".to_string()), 0, 5)],
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn keywords_are_reserved() {
+        let keywords = [
+            (let_key(), "let"),
+            (fn_key(), "fn"),
+            (if_key(), "if"),
+            (else_key(), "else"),
+            (loop_key(), "loop"),
+            (while_key(), "while"),
+            (for_key(), "for"),
+            (in_key(), "in"),
+            (break_key(), "break"),
+            (continue_key(), "continue"),
+            (struct_key(), "struct"),
+            (enum_key(), "enum"),
+            (match_key(), "match"),
+            (true_key(), "true"),
+            (false_key(), "false"),
+        ];
+
+        assert_eq!(keywords.len(), RESERVED.len());
+        for (key, word) in keywords {
+            assert!(RESERVED.contains(&word));
+            assert!(key.parse(word).is_ok());
+            assert!(identifier().parse(word).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_keyword_prefixed_names() {
+        assert_eq!(
+            statement().parse("letter = 5"),
+            Ok((
+                at(
+                    Statement::Assign(reference_at("letter", 0), None, integer_at("5", 9)),
+                    0,
+                    10
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            expression().parse("trueish || iffy(fnord)"),
+            Ok((
+                binary(
+                    BinaryOperator::Or,
+                    reference_at("trueish", 0),
+                    at(
+                        Expression::Call {
+                            callee: Box::new(reference_at("iffy", 11)),
+                            args: vec![reference_at("fnord", 16)],
+                        },
+                        11,
+                        22
+                    )
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            statement().parse("let while = 1").unwrap_err().to_string(),
+            "1:5: expected pattern, found keyword `while`"
+        );
+        assert_eq!(
+            statement().parse("let true = 1").unwrap_err().to_string(),
+            "1:5: refutable pattern in `let` binding"
+        );
     }
 
     #[test]
     fn parse_program() {
         assert_eq!(
             program().parse("let a;"),
-            Ok((Program(vec![Statement::Declare(None, "a".to_string())]), ""))
+            Ok((
+                Program(vec![at(Statement::Declare(None, "a".to_string()), 0, 5)]),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_program_with_comments() {
+        assert_eq!(
+            crate::program::parse_program("// header\nlet a /* unused */; // done\n/* end */")
+                .map(|(program, _)| program),
+            Ok(Program(vec![at(
+                Statement::Declare(None, "a".to_string()),
+                10,
+                15
+            )]))
+        );
+        assert_eq!(
+            expression().parse("a // b\n/ c"),
+            Ok((
+                binary(
+                    BinaryOperator::Divide,
+                    reference_at("a", 0),
+                    reference_at("c", 9)
+                ),
+                ""
+            ))
+        );
+    }
+
+    #[test]
+    fn program_errors_point_at_the_failure() {
+        let error =
+            |source| crate::program::parse_program(source).unwrap_err().errors[0].to_string();
+
+        assert_eq!(error("a + ;"), "1:5: expected expression, found `;`");
+        assert_eq!(
+            error("[1, 2"),
+            "1:6: expected `]` or `,`, found end of input"
+        );
+        assert_eq!(
+            error("let x = 1;\nfoo(1, ;"),
+            "2:8: expected expression or `)`, found `;`"
+        );
+        assert_eq!(error("{ a + ; };"), "1:7: expected expression, found `;`");
+        assert_eq!(
+            error("fn f(a: i32 b: i32) {};"),
+            "1:13: expected `)` or `,`, found `b`"
+        );
+        assert_eq!(
+            crate::program::parse_program(";;{;};").map(|(program, _)| program),
+            Ok(Program(vec![
+                at(Statement::NoOp, 0, 0),
+                at(Statement::NoOp, 1, 1),
+                at(
+                    Statement::Expression(at(
+                        Expression::Block(vec![at(Statement::NoOp, 3, 3)]),
+                        2,
+                        5
+                    )),
+                    2,
+                    5
+                ),
+            ]))
         );
     }
+
+    #[test]
+    fn parse_deeply_nested_program() {
+        let mut source = "a[0] = 1;".to_string();
+        for _ in 0..32 {
+            source = format!("{{{}}};", source);
+        }
+        assert!(crate::program::parse_program(&source).is_ok());
+
+        let source = format!("let a = {}1{};", "[".repeat(32), "]".repeat(32));
+        assert!(crate::program::parse_program(&source).is_ok());
+
+        let nested = |inner: &str| format!("{}{}{}", "(".repeat(32), inner, ",)".repeat(32));
+        let source = format!("let {}: {} = {};", nested("a"), nested("i32"), nested("1"));
+        assert!(crate::program::parse_program(&source).is_ok());
+    }
+
+    #[test]
+    fn parse_whole_program() {
+        assert_eq!(
+            crate::program::parse_program("let a;\nlet b = 2;\n"),
+            Ok(Program(vec![
+                at(Statement::Declare(None, "a".to_string()), 0, 5),
+                at(
+                    Statement::Instantiate(None, binding_at("b", 11), integer_at("2", 15)),
+                    7,
+                    16
+                ),
+            ]))
+            .map(|program| (
+                program,
+                Literals::from([(Span::new(15, 16), Number::I64(2))])
+            ))
+        );
+
+        let diagnostics = crate::program::parse_program("let a;\nlet b = 2 3;").unwrap_err();
+
+        assert_eq!(diagnostics.errors.len(), 1);
+        assert_eq!(
+            diagnostics.to_string(),
+            "error: 2:11: expected `;`, found `3`\n  |\n2 | let b = 2 3;\n  |           ^\n"
+        );
+    }
+
+    #[test]
+    fn evaluate_literals() {
+        use crate::program::evaluate::literal_value;
+
+        let integer = |text: &str, suffix| Literal::Integer(text.to_string(), suffix);
+
+        assert_eq!(
+            literal_value(&integer("0xff", None), false, None),
+            Some(Ok(Number::I64(255)))
+        );
+        assert_eq!(
+            literal_value(&integer("18446744073709551615", None), false, None),
+            Some(Ok(Number::U128(18446744073709551615)))
+        );
+        assert_eq!(
+            literal_value(&integer("9223372036854775809", None), true, None),
+            Some(Ok(Number::I128(-9223372036854775809)))
+        );
+        assert_eq!(
+            literal_value(&integer("1_000", Some(IntegerSuffix::U16)), false, None),
+            Some(Ok(Number::U64(1000)))
+        );
+        assert_eq!(
+            literal_value(
+                &integer("128", None),
+                true,
+                Some(&Type::Atomic("i8".to_string()))
+            ),
+            Some(Ok(Number::I64(-128)))
+        );
+        assert_eq!(
+            literal_value(&integer("128", Some(IntegerSuffix::I8)), false, None),
+            Some(Err(
+                "integer literal `128` is out of range for `i8`".to_string()
+            ))
+        );
+        assert_eq!(
+            literal_value(&integer("1", Some(IntegerSuffix::U8)), true, None),
+            Some(Err(
+                "integer literal `-1` is out of range for `u8`".to_string()
+            ))
+        );
+        assert_eq!(
+            literal_value(&Literal::Float("2.5".to_string(), None), true, None),
+            Some(Ok(Number::F64(-2.5)))
+        );
+        assert_eq!(literal_value(&Literal::Boolean(true), false, None), None);
+    }
+
+    #[test]
+    fn evaluate_program_literals() {
+        let (_, literals) =
+            crate::program::parse_program("let x: u8 = 255; let y = -1 + 2.5; f(1i128);").unwrap();
+
+        assert_eq!(
+            literals,
+            Literals::from([
+                (Span::new(12, 15), Number::U64(255)),
+                (Span::new(25, 27), Number::I64(-1)),
+                (Span::new(30, 33), Number::F64(2.5)),
+                (Span::new(37, 42), Number::I128(1)),
+            ])
+        );
+    }
+
+    #[test]
+    fn literal_overflow_diagnostics() {
+        let diagnostics = crate::program::parse_program("let x: u8 = 300;").unwrap_err();
+
+        assert_eq!(
+            diagnostics.to_string(),
+            "error: 1:13: integer literal `300` is out of range for `u8`\n  |\n1 | let x: u8 = 300;\n  |             ^\n"
+        );
+
+        assert_eq!(
+            crate::program::parse_program("let y = 1e39f32;")
+                .unwrap_err()
+                .errors[0]
+                .to_string(),
+            "1:9: float literal `1e39` is out of range for `f32`"
+        );
+        assert_eq!(
+            crate::program::parse_program("let z = 340282366920938463463374607431768211456;")
+                .unwrap_err()
+                .errors[0]
+                .to_string(),
+            "1:9: integer literal `340282366920938463463374607431768211456` is out of range for `u128`"
+        );
+        assert_eq!(
+            crate::program::parse_program("let a: [u8] = [1, 300];")
+                .unwrap_err()
+                .errors[0]
+                .to_string(),
+            "1:19: integer literal `300` is out of range for `u8`"
+        );
+        assert_eq!(
+            crate::program::parse_program("let t: (u8, i8) = (1, 300);")
+                .unwrap_err()
+                .errors[0]
+                .to_string(),
+            "1:23: integer literal `300` is out of range for `i8`"
+        );
+        assert!(crate::program::parse_program(
+            "let w: i8 = -128; let v: u64 = 0xffff_ffff_ffff_ffff;"
+        )
+        .is_ok());
+        let errors = crate::program::parse_program("match x { 300u8 => 1, (-129i8, _) => 2, };")
+            .unwrap_err()
+            .errors;
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "1:11: integer literal `300` is out of range for `u8`",
+                "1:24: integer literal `-129` is out of range for `i8`",
+            ]
+        );
+        assert!(crate::program::parse_program(
+            "let x: u64 = f(18446744073709551615); x == 18446744073709551615;"
+        )
+        .is_ok());
+    }
 }